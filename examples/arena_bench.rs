@@ -0,0 +1,49 @@
+//! Compares building a tree of 1,111,111 nodes with one allocation per node
+//! against building it in one contiguous buffer.
+//!
+//! Run with `cargo run --release --example arena_bench`.
+
+use std::time::{Duration, Instant};
+
+use trees::Tree;
+
+const FANOUT : usize = 10;
+const HEIGHT : usize = 7;
+const RUNS   : usize = 3;
+
+// `(depth, data)` pairs of a complete tree in pre-order.
+fn pairs() -> Vec<(usize,usize)> {
+    let mut pairs = Vec::new();
+    let mut stack = vec![ 0 ];
+    while let Some( depth ) = stack.pop() {
+        pairs.push(( depth, pairs.len() ));
+        if depth + 1 < HEIGHT {
+            stack.extend( std::iter::repeat( depth+1 ).take( FANOUT ));
+        }
+    }
+    pairs
+}
+
+fn measure( name: &str, build: fn( Vec<(usize,usize)> ) -> Tree<usize> ) {
+    for _ in 0..RUNS {
+        let pairs = pairs();
+
+        let start = Instant::now();
+        let tree = build( pairs );
+        let built = start.elapsed();
+
+        let node_count = tree.node_count();
+        let start = Instant::now();
+        drop( tree );
+        let dropped = start.elapsed();
+
+        println!( "{:<20} {} nodes: build {:>4} ms, drop {:>4} ms", name, node_count, ms( built ), ms( dropped ));
+    }
+}
+
+fn ms( duration: Duration ) -> u128 { duration.as_millis() }
+
+fn main() {
+    measure( "from_depths_in_arena", |pairs| Tree::from_depths_in_arena( pairs ).unwrap() );
+    measure( "from_depths", |pairs| Tree::from_depths( pairs ).unwrap() );
+}
@@ -0,0 +1,157 @@
+//! Compact binary encoding of trees, without serde.
+//!
+//! Nodes are written in pre-order, each as its number of children in a little-endian `u64`
+//! followed by its data encoded with `ToBytes`.
+
+use crate::rust::*;
+
+use std::io::{self, Read, Write};
+
+use super::Tree;
+
+/// Encodes a value as bytes, for `Tree::write_binary`.
+pub trait ToBytes {
+    fn write_bytes<W:Write>( &self, w: &mut W ) -> io::Result<()>;
+}
+
+/// Decodes a value written by `ToBytes`, for `Tree::read_binary`.
+pub trait FromBytes: Sized {
+    fn read_bytes<R:Read>( r: &mut R ) -> io::Result<Self>;
+}
+
+macro_rules! impl_bytes_for_integers {
+    ($($ty:ty)*) => {$(
+        impl ToBytes for $ty {
+            fn write_bytes<W:Write>( &self, w: &mut W ) -> io::Result<()> { w.write_all( &self.to_le_bytes() )}
+        }
+
+        impl FromBytes for $ty {
+            fn read_bytes<R:Read>( r: &mut R ) -> io::Result<Self> {
+                let mut bytes = [0; mem::size_of::<$ty>()];
+                r.read_exact( &mut bytes )?;
+                Ok( <$ty>::from_le_bytes( bytes ))
+            }
+        }
+    )*};
+}
+
+impl_bytes_for_integers!( u8 u16 u32 u64 u128 i8 i16 i32 i64 i128 );
+
+/// Written as the length in a little-endian `u64` followed by the UTF-8 bytes.
+impl ToBytes for String {
+    fn write_bytes<W:Write>( &self, w: &mut W ) -> io::Result<()> {
+        ( self.len() as u64 ).write_bytes( w )?;
+        w.write_all( self.as_bytes() )
+    }
+}
+
+impl FromBytes for String {
+    fn read_bytes<R:Read>( r: &mut R ) -> io::Result<Self> {
+        let len = read_len( r )?;
+        let mut bytes = Vec::new();
+        r.take( len as u64 ).read_to_end( &mut bytes )?;
+        if bytes.len() != len {
+            return Err( io::ErrorKind::UnexpectedEof.into() );
+        }
+        String::from_utf8( bytes ).map_err( |err| io::Error::new( io::ErrorKind::InvalidData, err ))
+    }
+}
+
+fn read_len<R:Read>( r: &mut R ) -> io::Result<usize> {
+    let len = u64::read_bytes( r )?;
+    usize::try_from( len ).map_err( |err| io::Error::new( io::ErrorKind::InvalidData, err ))
+}
+
+impl<T:ToBytes> Tree<T> {
+    /// Writes the tree in the binary format described in the [module document](../binary/index.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut bytes = Vec::new();
+    /// ( tr(1u8) /tr(2u8) ).write_binary( &mut bytes ).unwrap();
+    /// assert_eq!( bytes, vec![ 1,0,0,0,0,0,0,0, 1, 0,0,0,0,0,0,0,0, 2 ]);
+    /// ```
+    pub fn write_binary<W:Write>( &self, w: &mut W ) -> io::Result<()> {
+        for (_, node) in self.iter_depth() {
+            ( node.degree() as u64 ).write_bytes( w )?;
+            node.data().write_bytes( w )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T:FromBytes> Tree<T> {
+    /// Reads a tree written by [`write_binary`](#method.write_binary).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = tr(0u32) /( tr(1)/tr(2) ) /tr(3);
+    /// let mut bytes = Vec::new();
+    /// tree.write_binary( &mut bytes ).unwrap();
+    /// assert_eq!( Tree::<u32>::read_binary( &mut bytes.as_slice() ).unwrap(), tree );
+    /// ```
+    pub fn read_binary<R:Read>( r: &mut R ) -> io::Result<Tree<T>> {
+        let mut stack = Vec::<(Tree<T>, usize)>::new();
+        loop {
+            let degree = read_len( r )?;
+            stack.push(( Tree::new( T::read_bytes( r )? ), degree ));
+            loop {
+                match stack.last_mut() {
+                    Some(( _, remaining )) if *remaining == 0 => {
+                        let (tree, _) = stack.pop().unwrap();
+                        match stack.last_mut() {
+                            Some(( parent, remaining )) => {
+                                parent.push_back( tree );
+                                *remaining -= 1;
+                            },
+                            None => return Ok( tree ),
+                        }
+                    },
+                    _ => break,
+                }
+            }
+        }
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+    use crate::tr;
+
+    #[test] fn binary_round_trip() {
+        let tree = tr(0u32) /( tr(1)/( tr(2)/tr(3) )/tr(4) ) /tr(u32::MAX) /( tr(6)/tr(7) );
+        let mut bytes = Vec::new();
+        tree.write_binary( &mut bytes ).unwrap();
+        assert_eq!( bytes.len(), tree.node_count() * (8+4) );
+        let mut cursor = io::Cursor::new( bytes );
+        let read = Tree::<u32>::read_binary( &mut cursor ).unwrap();
+        assert_eq!( read, tree );
+        assert!( read.validate().is_ok() );
+        assert_eq!( cursor.position() as usize, cursor.get_ref().len() );
+
+        let tree = tr( "root".to_owned() ) /tr( String::new() ) /tr( "κλάδος".to_owned() );
+        let mut bytes = Vec::new();
+        tree.write_binary( &mut bytes ).unwrap();
+        assert_eq!( Tree::<String>::read_binary( &mut bytes.as_slice() ).unwrap(), tree );
+    }
+
+    #[test] fn binary_errors() {
+        let mut bytes = Vec::new();
+        ( tr(1i16) /tr(2) /tr(3) ).write_binary( &mut bytes ).unwrap();
+        for len in 0..bytes.len() {
+            assert_eq!( Tree::<i16>::read_binary( &mut &bytes[ ..len ]).unwrap_err().kind(), io::ErrorKind::UnexpectedEof );
+        }
+
+        let mut bytes = Vec::new();
+        tr( "a".to_owned() ).write_binary( &mut bytes ).unwrap();
+        *bytes.last_mut().unwrap() = 0xff;
+        assert_eq!( Tree::<String>::read_binary( &mut bytes.as_slice() ).unwrap_err().kind(), io::ErrorKind::InvalidData );
+    }
+}
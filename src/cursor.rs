@@ -0,0 +1,318 @@
+//! Cursor over a forest's children, returned by `cursor_front()`.
+
+use crate::rust::*;
+
+use super::{Forest, Node, Tree};
+
+/// A cursor over the children of a `Forest`, allowing in-place editing.
+///
+/// Like `std::collections::linked_list::CursorMut`, the cursor always rests on
+/// an element or on a "ghost" position between the last and the first child.
+///
+/// # Examples
+///
+/// ```
+/// use trees::tr;
+///
+/// let mut forest = -tr(1)-tr(2)-tr(3);
+/// let mut cursor = forest.cursor_front();
+/// cursor.move_next();
+/// assert_eq!( cursor.remove_current(), Some( tr(2) ));
+/// cursor.insert_before( tr(4) );
+/// assert_eq!( cursor.current().map( |node| *node.data() ), Some(3) );
+/// assert_eq!( forest, -tr(1)-tr(4)-tr(3) );
+/// ```
+pub struct Cursor<'a, T:'a> {
+    forest : &'a mut Forest<T>,
+    curr   : Option<NonNull<Node<T>>>,
+    index  : usize, // of `curr`, or the degree at the ghost position.
+}
+
+impl<'a, T:'a> Cursor<'a,T> {
+    pub(crate) fn new( forest: &'a mut Forest<T>, curr: Option<NonNull<Node<T>>> ) -> Self {
+        Cursor{ forest, curr, index: 0 }
+    }
+
+    /// Returns the index of the child the cursor is pointing to, counting from 0 at the first child,
+    /// or `None` if it is at the ghost position.
+    ///
+    /// The index follows the edits made through the cursor: inserting before the current child increases it,
+    /// while inserting after it or removing it leaves it unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -tr(1)-tr(2);
+    /// let mut cursor = forest.cursor_front();
+    /// assert_eq!( cursor.index(), Some(0) );
+    /// cursor.move_next();
+    /// assert_eq!( cursor.index(), Some(1) );
+    /// cursor.insert_before( tr(3) );
+    /// assert_eq!( cursor.index(), Some(2) );
+    /// cursor.move_next();
+    /// assert_eq!( cursor.index(), None );
+    /// ```
+    pub fn index( &self ) -> Option<usize> {
+        self.curr.map( |_| self.index )
+    }
+
+    /// Returns a reference to the child the cursor is pointing to,
+    /// or `None` if it is at the ghost position.
+    pub fn current( &self ) -> Option<&Node<T>> {
+        self.curr.map( |curr| unsafe{ &*curr.as_ptr() })
+    }
+
+    /// Returns a mutable pointer to the child the cursor is pointing to,
+    /// or `None` if it is at the ghost position.
+    pub fn current_mut( &mut self ) -> Option<Pin<&mut Node<T>>> {
+        self.curr.map( |curr| unsafe{ Pin::new_unchecked( &mut *curr.as_ptr() )})
+    }
+
+    /// Moves the cursor to the next child.
+    /// From the last child it moves to the ghost position, and from the ghost position to the first child.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -tr(1)-tr(2);
+    /// let mut cursor = forest.cursor_front();
+    /// cursor.move_next();
+    /// assert_eq!( cursor.current().map( |node| *node.data() ), Some(2) );
+    /// cursor.move_next();
+    /// assert!( cursor.current().is_none() );
+    /// cursor.move_next();
+    /// assert_eq!( cursor.current().map( |node| *node.data() ), Some(1) );
+    /// ```
+    pub fn move_next( &mut self ) {
+        match self.curr {
+            Some( curr ) => {
+                self.curr = unsafe{ curr.as_ref().next };
+                self.index += 1;
+            },
+            None => {
+                self.curr = self.forest.root_().head;
+                self.index = 0;
+            },
+        }
+    }
+
+    /// Moves the cursor to the previous child.
+    /// From the first child it moves to the ghost position, and from the ghost position to the last child.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -tr(1)-tr(2);
+    /// let mut cursor = forest.cursor_front();
+    /// cursor.move_prev();
+    /// assert!( cursor.current().is_none() );
+    /// cursor.move_prev();
+    /// assert_eq!( cursor.current().map( |node| *node.data() ), Some(2) );
+    /// ```
+    pub fn move_prev( &mut self ) {
+        match self.curr {
+            Some( curr ) => {
+                self.curr = unsafe{ curr.as_ref().prev };
+                self.index = if self.curr.is_some() { self.index - 1 } else { self.forest.degree() };
+            },
+            None => {
+                self.curr = self.forest.root_().tail;
+                self.index = self.forest.degree().saturating_sub( 1 );
+            },
+        }
+    }
+
+    /// Inserts the tree after the current child.
+    /// At the ghost position the tree becomes the first child.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{fr, tr};
+    ///
+    /// let mut forest = fr();
+    /// let mut cursor = forest.cursor_front();
+    /// cursor.insert_after( tr(2) );
+    /// cursor.insert_after( tr(1) );
+    /// assert_eq!( forest.to_string(), "( 1 2 )" );
+    /// ```
+    pub fn insert_after( &mut self, tree: Tree<T> ) {
+        match self.curr {
+            Some( mut curr ) => unsafe{ curr.as_mut().insert_next_sib( tree )},
+            None => {
+                self.forest.push_front( tree );
+                self.index += 1;
+            },
+        }
+    }
+
+    /// Inserts the tree before the current child.
+    /// At the ghost position the tree becomes the last child.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{fr, tr};
+    ///
+    /// let mut forest = fr();
+    /// let mut cursor = forest.cursor_front();
+    /// cursor.insert_before( tr(1) );
+    /// cursor.insert_before( tr(2) );
+    /// assert_eq!( forest.to_string(), "( 1 2 )" );
+    /// ```
+    pub fn insert_before( &mut self, tree: Tree<T> ) {
+        match self.curr {
+            Some( mut curr ) => unsafe{ curr.as_mut().insert_prev_sib( tree )},
+            None => self.forest.push_back( tree ),
+        }
+        self.index += 1;
+    }
+
+    /// Inserts a deep clone of the current child after it, and moves the cursor to the clone,
+    /// so that the following `move_next()` skips the clone.
+    /// Does nothing at the ghost position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -tr(1)-tr(2);
+    /// let mut cursor = forest.cursor_front();
+    /// while cursor.current().is_some() {
+    ///     cursor.duplicate_after();
+    ///     cursor.move_next();
+    /// }
+    /// assert_eq!( forest.to_string(), "( 1 1 2 2 )" );
+    /// ```
+    pub fn duplicate_after( &mut self )
+        where T: Clone
+    {
+        if let Some( curr ) = self.current() {
+            let clone = curr.deep_clone();
+            self.insert_after( clone );
+            self.move_next();
+        }
+    }
+
+    /// Removes the current child and returns it, moving the cursor to the next child.
+    /// Returns `None` at the ghost position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -tr(1)-tr(2);
+    /// let mut cursor = forest.cursor_front();
+    /// assert_eq!( cursor.remove_current(), Some( tr(1) ));
+    /// assert_eq!( cursor.current().map( |node| *node.data() ), Some(2) );
+    /// assert_eq!( cursor.remove_current(), Some( tr(2) ));
+    /// assert_eq!( cursor.remove_current(), None );
+    /// ```
+    pub fn remove_current( &mut self ) -> Option<Tree<T>> {
+        self.curr.map( |mut curr| unsafe {
+            self.curr = curr.as_ref().next;
+            curr.as_mut().detach()
+        })
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::tr;
+
+    #[test] fn inserts_and_removals() {
+        let mut forest = -tr(1)-tr(2)-tr(3)-tr(4);
+        {
+            let mut cursor = forest.cursor_front();
+            cursor.insert_before( tr(0) );
+            cursor.move_next();
+            assert_eq!( cursor.remove_current(), Some( tr(2) ));
+            cursor.insert_after( tr(5)/tr(6) );
+            cursor.move_next();
+            cursor.move_next();
+            assert_eq!( cursor.remove_current(), Some( tr(4) ));
+            assert!( cursor.current().is_none() );
+            cursor.insert_after( tr(-1) );
+            cursor.move_prev();
+            cursor.current_mut().unwrap().push_back( tr(7) );
+            cursor.move_prev();
+            cursor.move_prev();
+            assert_eq!( cursor.remove_current(), Some( tr(1) ));
+        }
+        assert_eq!( forest, -tr(-1)-tr(0)-tr(3)-( tr(5)/tr(6)/tr(7) ));
+        assert_eq!( forest.node_count(), 6 );
+    }
+
+    #[test] fn duplicate_after() {
+        use crate::{Tree, fr};
+        use crate::rust::*;
+
+        let mut forest = ( tr(0)/tr(1)/( tr(2)/tr(3) ) ).into_forest();
+        {
+            let mut cursor = forest.cursor_front();
+            while cursor.current().is_some() {
+                cursor.duplicate_after();
+                assert_eq!( cursor.index().map( |index| index % 2 ), Some(1) );
+                cursor.move_next();
+            }
+            cursor.duplicate_after();
+            assert_eq!( cursor.index(), None );
+        }
+        let tree = Tree::from_forest( 0, forest );
+        assert_eq!( tree.to_string(), "0( 1 1 2( 3 ) 2( 3 ) )" );
+        assert_eq!( tree.node_count(), 7 );
+        assert!( tree.validate().is_ok() );
+
+        let mut forest = fr::<i32>();
+        forest.cursor_front().duplicate_after();
+        assert_eq!( forest, fr() );
+    }
+
+    #[test] fn index() {
+        use crate::rust::*;
+
+        let mut forest = -tr('a')-tr('b')-tr('c');
+        let mut cursor = forest.cursor_front();
+        let mut indices = Vec::new();
+        while let Some( index ) = cursor.index() {
+            indices.push(( index, *cursor.current().unwrap().data() ));
+            cursor.move_next();
+        }
+        assert_eq!( indices, vec![ (0,'a'), (1,'b'), (2,'c') ]);
+
+        cursor.insert_after( tr('z') );
+        assert_eq!( cursor.index(), None );
+        cursor.move_prev();
+        assert_eq!(( cursor.index(), cursor.current().map( |node| *node.data() )), ( Some(3), Some('c') ));
+        cursor.insert_before( tr('y') );
+        assert_eq!( cursor.index(), Some(4) );
+        assert_eq!( cursor.remove_current(), Some( tr('c') ));
+        assert_eq!( cursor.index(), None );
+        cursor.insert_before( tr('x') );
+        cursor.move_prev();
+        assert_eq!(( cursor.index(), cursor.current().map( |node| *node.data() )), ( Some(4), Some('x') ));
+        cursor.move_next();
+        cursor.move_next();
+        assert_eq!(( cursor.index(), cursor.current().map( |node| *node.data() )), ( Some(0), Some('z') ));
+        cursor.move_prev();
+        cursor.move_prev();
+        assert_eq!( cursor.index(), Some(4) );
+        assert_eq!( forest.to_string(), "( z a b y x )" );
+
+        let mut forest = crate::fr::<i32>();
+        let mut cursor = forest.cursor_front();
+        cursor.move_prev();
+        assert_eq!( cursor.index(), None );
+        cursor.insert_before( tr(1) );
+        cursor.move_next();
+        assert_eq!( cursor.index(), Some(0) );
+    }
+}
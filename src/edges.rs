@@ -0,0 +1,103 @@
+//! Building trees from parent-pointer edge lists, as commonly stored in databases.
+
+use crate::rust::*;
+
+use std::collections::HashMap;
+
+use super::{Error, Tree};
+
+impl<T> Tree<T> {
+    /// Builds a tree from the values of nodes keyed by their ids, and the id of each node's parent keyed by the node's id.
+    ///
+    /// Every node except `root` must have exactly one entry in `parents`, and every id mentioned must have a value.
+    /// Returns an error if `root` has no value or has a parent, if some node has no value or no parent,
+    /// or if some nodes are not reachable from `root` because their parents form a cycle.
+    ///
+    /// The order of siblings is unspecified, since `HashMap`s do not keep the order of insertion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    /// use std::collections::HashMap;
+    ///
+    /// let values = vec![ (1,'a'), (2,'b'), (3,'c') ].into_iter().collect::<HashMap<_,_>>();
+    /// let parents = vec![ (2,1), (3,2) ].into_iter().collect::<HashMap<_,_>>();
+    /// assert_eq!( Tree::from_edges( 1, values, parents ).unwrap(), tr('a') /( tr('b')/tr('c') ));
+    ///
+    /// let values = vec![ (1,'a'), (2,'b') ].into_iter().collect::<HashMap<_,_>>();
+    /// let parents = vec![ (2,3) ].into_iter().collect::<HashMap<_,_>>();
+    /// assert_eq!( Tree::from_edges( 1, values, parents ).unwrap_err().to_string(), "edges: dangling parent" );
+    /// ```
+    pub fn from_edges<Id>( root: Id, mut values: HashMap<Id,T>, parents: HashMap<Id,Id> ) -> Result<Tree<T>, Error>
+        where Id: Eq + Hash + Copy
+    {
+        if !values.contains_key( &root ) {
+            return Err( "edges: the root has no value".into() );
+        }
+        if parents.contains_key( &root ) {
+            return Err( "edges: the root has a parent".into() );
+        }
+
+        let mut children = HashMap::<Id,Vec<Id>>::new();
+        for (&child, &parent) in &parents {
+            if !values.contains_key( &child ) {
+                return Err( "edges: a node has no value".into() );
+            }
+            if !values.contains_key( &parent ) {
+                return Err( "edges: dangling parent".into() );
+            }
+            children.entry( parent ).or_default().push( child );
+        }
+        if parents.len() + 1 != values.len() {
+            return Err( "edges: a node other than the root has no parent".into() );
+        }
+
+        let mut pairs = Vec::with_capacity( values.len() );
+        let mut stack = vec![ (root, 0) ];
+        while let Some( (id, depth) ) = stack.pop() {
+            pairs.push(( depth, values.remove( &id ).unwrap() ));
+            if let Some( ids ) = children.get( &id ) {
+                stack.extend( ids.iter().rev().map( |&child| (child, depth+1) ));
+            }
+        }
+        if !values.is_empty() {
+            return Err( "edges: cycle detected".into() );
+        }
+        Ok( Tree::from_depths( pairs ).unwrap() )
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+    use crate::tr;
+
+    fn edges( values: &[(u32,i32)], parents: &[(u32,u32)] ) -> Result<Tree<i32>, String> {
+        Tree::from_edges( 0, values.iter().cloned().collect(), parents.iter().cloned().collect() )
+            .map_err( |err| err.msg )
+    }
+
+    #[test] fn from_edges() {
+        let tree = edges( &[ (0,10), (1,11), (2,12), (3,13) ], &[ (1,0), (2,1), (3,0) ]).unwrap();
+        assert!( tree.eq_unordered( &( tr(10) /( tr(11)/tr(12) ) /tr(13) )));
+        assert_eq!( tree.node_count(), 4 );
+        assert!( tree.validate().is_ok() );
+        assert_eq!( edges( &[ (0,10) ], &[] ).unwrap(), tr(10) );
+
+        let values = (0..1000).map( |id| (id, id as i32) ).collect::<Vec<_>>();
+        let parents = (1..1000).map( |id| (id, id-1) ).collect::<Vec<_>>();
+        let chain = edges( &values, &parents ).unwrap();
+        assert_eq!( chain.iter_depth().map( |(depth, node)| (depth, *node.data() as usize) ).last(), Some(( 999, 999 )));
+    }
+
+    #[test] fn from_edges_errors() {
+        assert_eq!( edges( &[ (1,11), (2,12) ], &[] ).unwrap_err(), "edges: the root has no value" );
+        assert_eq!( edges( &[ (0,10), (1,11) ], &[ (0,1), (1,0) ]).unwrap_err(), "edges: the root has a parent" );
+        assert_eq!( edges( &[ (0,10) ], &[ (1,0) ]).unwrap_err(), "edges: a node has no value" );
+        assert_eq!( edges( &[ (0,10), (1,11) ], &[ (1,2) ]).unwrap_err(), "edges: dangling parent" );
+        assert_eq!( edges( &[ (0,10), (1,11), (2,12) ], &[ (1,0) ]).unwrap_err(), "edges: a node other than the root has no parent" );
+        assert_eq!( edges( &[ (0,10), (1,11), (2,12), (3,13) ], &[ (1,0), (2,3), (3,2) ]).unwrap_err(), "edges: cycle detected" );
+        assert_eq!( edges( &[ (0,10), (1,11) ], &[ (1,1) ]).unwrap_err(), "edges: cycle detected" );
+    }
+}
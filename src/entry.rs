@@ -0,0 +1,123 @@
+//! Entry of a child looked up by data, returned by `child_entry()`.
+
+use crate::rust::*;
+
+use super::{Node, Tree};
+
+/// A view into a child of a node looked up by data, which may be missing.
+///
+/// # Examples
+///
+/// ```
+/// use trees::tr;
+///
+/// let mut tree = tr("/");
+/// for path in &[ "usr/bin", "usr/lib", "etc" ] {
+///     let mut node = tree.root_mut();
+///     for seg in path.split('/') {
+///         node = node.child_entry( &seg ).or_insert();
+///     }
+/// }
+/// assert_eq!( tree.to_string(), "/( usr( bin lib ) etc )" );
+/// ```
+pub struct ChildEntry<'a, 'k, T:'a> {
+    parent : Pin<&'a mut Node<T>>,
+    key    : &'k T,
+    child  : Option<NonNull<Node<T>>>,
+}
+
+impl<'a, 'k, T:'a> ChildEntry<'a,'k,T> {
+    pub(crate) fn new( parent: Pin<&'a mut Node<T>>, key: &'k T, child: Option<NonNull<Node<T>>> ) -> Self {
+        ChildEntry{ parent, key, child }
+    }
+
+    /// Returns `true` if the child exists.
+    pub fn is_occupied( &self ) -> bool { self.child.is_some() }
+
+    /// Returns the key the child is looked up by.
+    pub fn key( &self ) -> &'k T { self.key }
+
+    /// Returns a mutable pointer to the child, or `None` if it does not exist.
+    pub fn get( self ) -> Option<Pin<&'a mut Node<T>>> {
+        self.child.map( |child| unsafe{ Pin::new_unchecked( &mut *child.as_ptr() )})
+    }
+
+    /// Returns a mutable pointer to the child, appending a new leaf node of a clone of the key as the last child if it does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1);
+    /// tree.root_mut().child_entry( &1 ).or_insert().push_back( tr(2) );
+    /// tree.root_mut().child_entry( &3 ).or_insert().push_back( tr(4) );
+    /// assert_eq!( tree.to_string(), "0( 1( 2 ) 3( 4 ) )" );
+    /// ```
+    pub fn or_insert( self ) -> Pin<&'a mut Node<T>>
+        where T: Clone
+    {
+        let key = self.key;
+        self.or_insert_with( || Tree::new( key.clone() ))
+    }
+
+    /// Returns a mutable pointer to the child, appending the tree made by `f` as the last child if it does not exist.
+    /// The new tree's root data is not checked against the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1);
+    /// assert_eq!( tree.root_mut().child_entry( &1 ).or_insert_with( || tr(2) ).data(), &1 );
+    /// assert_eq!( tree.root_mut().child_entry( &3 ).or_insert_with( || tr(3)/tr(4) ).degree(), 1 );
+    /// assert_eq!( tree.to_string(), "0( 1 3( 4 ) )" );
+    /// ```
+    pub fn or_insert_with<F>( self, f: F ) -> Pin<&'a mut Node<T>>
+        where F: FnOnce() -> Tree<T>
+    {
+        let child = match self.child {
+            Some( child ) => child,
+            None => unsafe {
+                let parent = Pin::get_unchecked_mut( self.parent );
+                parent.push_back( f() );
+                parent.tail.unwrap()
+            },
+        };
+        unsafe{ Pin::new_unchecked( &mut *child.as_ptr() )}
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::rust::*;
+    use crate::{Tree, tr};
+
+    fn insert_path( tree: &mut Tree<String>, path: &str ) {
+        let mut node = tree.root_mut();
+        for seg in path.split( '/' ) {
+            node = node.child_entry( &seg.to_owned() ).or_insert();
+        }
+    }
+
+    #[test] fn overlapping_paths() {
+        let mut tree = tr( String::new() );
+        for path in &[ "a/b/c", "a/b/d", "a/e", "a/b/c", "f", "a/b/c/g" ] {
+            insert_path( &mut tree, path );
+        }
+        assert_eq!( tree.to_string(), "( a( b( c( g ) d ) e ) f )" );
+        assert_eq!( tree.node_count(), 8 );
+        assert!( tree.validate().is_ok() );
+
+        let (a, x) = ("a".to_owned(), "x".to_owned());
+        let entry = tree.root_mut().child_entry( &a );
+        assert!( entry.is_occupied() );
+        assert_eq!( entry.get().map( |node| node.node_count() ), Some(6) );
+        let entry = tree.root_mut().child_entry( &x );
+        assert!( !entry.is_occupied() );
+        assert_eq!( entry.key(), "x" );
+        assert!( entry.get().is_none() );
+        assert_eq!( tree.degree(), 2 );
+    }
+}
@@ -0,0 +1,245 @@
+//! Pull-based parsing of trees in `Display` format, without building the tree.
+//!
+//! Each node is reported as a `TreeEvent::Open` carrying its data, followed by the events of its children,
+//! and then a `TreeEvent::Close`, in the same manner as `Visit::Begin` and `Visit::End` of `TreeWalk`.
+
+use crate::rust::*;
+
+use std::io::{BufReader, Bytes, Read};
+
+use super::Error;
+
+/// Event of parsing a tree.
+#[derive( Clone, Debug, PartialEq, Eq )]
+pub enum TreeEvent<T> {
+    /// Begin of a node, with its data.
+    Open( T ),
+    /// End of the most recently opened node which is not closed yet.
+    Close,
+}
+
+/// Parses a tree in `Display` format such as `0( 1( 2 3 ) 4 )` from `input`,
+/// reading it only as far as needed to yield the next event.
+///
+/// Data are separated by whitespace or parentheses. An error ends the iteration.
+///
+/// # Examples
+///
+/// ```
+/// use trees::{TreeEvent, parse_events};
+///
+/// let events = parse_events( "1( 2 3 )".as_bytes() ).collect::<Result<Vec<_>,_>>().unwrap();
+/// assert_eq!( events, vec![
+///     TreeEvent::Open( "1".to_owned() ),
+///         TreeEvent::Open( "2".to_owned() ), TreeEvent::Close,
+///         TreeEvent::Open( "3".to_owned() ), TreeEvent::Close,
+///     TreeEvent::Close,
+/// ]);
+///
+/// let mut events = parse_events( "1( 2 ) 3".as_bytes() );
+/// assert!( events.by_ref().take(4).all( |event| event.is_ok() ));
+/// assert_eq!( events.next().unwrap().unwrap_err().to_string(), "events: more than one root" );
+/// assert!( events.next().is_none() );
+/// ```
+pub fn parse_events<R:Read>( input: R ) -> Events<R> {
+    Events {
+        bytes    : BufReader::new( input ).bytes(),
+        peeked   : None,
+        queue    : VecDeque::new(),
+        depth    : 0,
+        is_leaf  : false,
+        has_root : false,
+        done     : false,
+    }
+}
+
+/// An iterator over the events of parsing a tree.
+///
+/// This `struct` is created by [`parse_events`].
+/// See its document for more.
+///
+/// [`parse_events`]: fn.parse_events.html
+pub struct Events<R> {
+    bytes    : Bytes<BufReader<R>>,
+    peeked   : Option<u8>,               // parenthesis read while scanning data.
+    queue    : VecDeque<TreeEvent<String>>,
+    depth    : usize,                    // count of unclosed parentheses.
+    is_leaf  : bool,                     // the last token is data, not followed by '(' yet.
+    has_root : bool,
+    done     : bool,
+}
+
+enum Token {
+    Data( String ),
+    Open,
+    Close,
+}
+
+impl<R:Read> Events<R> {
+    fn next_byte( &mut self ) -> Result<Option<u8>, Error> {
+        match self.peeked.take() {
+            Some( byte ) => Ok( Some( byte )),
+            None => self.bytes.next().transpose().map_err( |err| format!( "events: {}", err ).into() ),
+        }
+    }
+
+    fn next_token( &mut self ) -> Result<Option<Token>, Error> {
+        let mut data = Vec::new();
+        while let Some( byte ) = self.next_byte()? {
+            match byte {
+                b'(' | b')' if !data.is_empty() => {
+                    self.peeked = Some( byte );
+                    break;
+                },
+                b'(' => return Ok( Some( Token::Open )),
+                b')' => return Ok( Some( Token::Close )),
+                _ if byte.is_ascii_whitespace() => if !data.is_empty() { break; },
+                _ => data.push( byte ),
+            }
+        }
+        if data.is_empty() {
+            Ok( None )
+        } else {
+            String::from_utf8( data )
+                .map( |data| Some( Token::Data( data )))
+                .map_err( |_| "events: data is not valid UTF-8".into() )
+        }
+    }
+
+    // Reads tokens until some events are queued.
+    fn parse( &mut self ) -> Result<(), Error> {
+        while self.queue.is_empty() && !self.done {
+            match self.next_token()? {
+                Some( Token::Data( data )) => {
+                    if self.is_leaf {
+                        self.queue.push_back( TreeEvent::Close );
+                    }
+                    if self.depth == 0 {
+                        if self.has_root {
+                            return Err( "events: more than one root".into() );
+                        }
+                        self.has_root = true;
+                    }
+                    self.queue.push_back( TreeEvent::Open( data ));
+                    self.is_leaf = true;
+                },
+                Some( Token::Open ) => {
+                    if !self.is_leaf {
+                        return Err( "events: unexpected '('".into() );
+                    }
+                    self.depth += 1;
+                    self.is_leaf = false;
+                },
+                Some( Token::Close ) => {
+                    if self.depth == 0 {
+                        return Err( "events: unexpected ')'".into() );
+                    }
+                    if self.is_leaf {
+                        self.queue.push_back( TreeEvent::Close );
+                    }
+                    self.queue.push_back( TreeEvent::Close );
+                    self.depth -= 1;
+                    self.is_leaf = false;
+                },
+                None => {
+                    if self.depth != 0 {
+                        return Err( "events: missing ')'".into() );
+                    }
+                    if !self.has_root {
+                        return Err( "events: no root".into() );
+                    }
+                    if self.is_leaf {
+                        self.queue.push_back( TreeEvent::Close );
+                    }
+                    self.done = true;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R:Read> Iterator for Events<R> {
+    type Item = Result<TreeEvent<String>, Error>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        if let Err( err ) = self.parse() {
+            self.queue.clear();
+            self.done = true;
+            return Some( Err( err ));
+        }
+        self.queue.pop_front().map( Ok )
+    }
+}
+
+impl<R:Read> FusedIterator for Events<R> {}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+    use crate::{Tree, tr};
+
+    // Yields one byte per read, to make sure events do not depend on how the input is chunked.
+    struct Trickle<'a>( &'a [u8] );
+
+    impl Read for Trickle<'_> {
+        fn read( &mut self, buf: &mut [u8] ) -> std::io::Result<usize> {
+            match self.0.split_first() {
+                Some(( &byte, rest )) if !buf.is_empty() => {
+                    buf[0] = byte;
+                    self.0 = rest;
+                    Ok(1)
+                },
+                _ => Ok(0),
+            }
+        }
+    }
+
+    fn events( s: &str ) -> Result<Vec<TreeEvent<String>>, String> {
+        parse_events( Trickle( s.as_bytes() ))
+            .collect::<Result<Vec<_>,_>>()
+            .map_err( |err| err.msg )
+    }
+
+    fn open( data: &str ) -> TreeEvent<String> { TreeEvent::Open( data.to_owned() )}
+
+    #[test] fn streamed_events() {
+        use TreeEvent::Close;
+
+        assert_eq!( events( "1( 2 3 )" ).unwrap(), vec![ open("1"), open("2"), Close, open("3"), Close, Close ]);
+        assert_eq!( events( " a " ).unwrap(), vec![ open("a"), Close ]);
+        assert_eq!( events( "0(1(2 3bc)4( 5\n6 ))" ).unwrap(), vec![
+            open("0"), open("1"), open("2"), Close, open("3bc"), Close, Close,
+            open("4"), open("5"), Close, open("6"), Close, Close, Close ]);
+    }
+
+    #[test] fn events_of_displayed_tree() {
+        let tree = tr(0) /( tr(1)/tr(2)/( tr(3)/tr(4) )) /tr(5);
+        let mut depth = 0;
+        let mut count = 0;
+        for event in parse_events( tree.to_string().as_bytes() ) {
+            match event.unwrap() {
+                TreeEvent::Open( data ) => {
+                    depth += 1;
+                    count += 1;
+                    assert!( tree.root().iter_descendants().chain( Some( tree.root() ))
+                        .any( |node| node.data().to_string() == data ));
+                },
+                TreeEvent::Close => depth -= 1,
+            }
+        }
+        assert_eq!( depth, 0 );
+        assert_eq!( count, tree.node_count() );
+        assert_eq!( Tree::try_from( tree.to_string() ).unwrap().to_string(), tree.to_string() );
+    }
+
+    #[test] fn event_errors() {
+        assert_eq!( events( "" ).unwrap_err(), "events: no root" );
+        assert_eq!( events( "1( 2" ).unwrap_err(), "events: missing ')'" );
+        assert_eq!( events( "1 )" ).unwrap_err(), "events: unexpected ')'" );
+        assert_eq!( events( "( 1 )" ).unwrap_err(), "events: unexpected '('" );
+        assert_eq!( events( "1( ( 2 ) )" ).unwrap_err(), "events: unexpected '('" );
+        assert_eq!( events( "1 2" ).unwrap_err(), "events: more than one root" );
+        assert_eq!( parse_events( &b"1( \xff )"[..] ).nth(1).unwrap().unwrap_err().msg, "events: data is not valid UTF-8" );
+    }
+}
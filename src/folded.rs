@@ -0,0 +1,63 @@
+//! Folded stacks export, the input format of flame graph tools.
+//!
+//! Each leaf node becomes a line listing the labels from the root down to the leaf separated by `';'`,
+//! followed by a space and the leaf's weight, e.g. `main;parse;read 5`.
+
+use crate::rust::*;
+
+use super::Tree;
+
+impl<T> Tree<T> {
+    /// Exports the tree as folded stacks, one line per leaf in pre-order, each terminated with `'\n'`.
+    ///
+    /// The closure maps each node's data to its label and weight. Only the weights of leaves are written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(( "main", 0 )) /( tr(( "parse", 2 ))/tr(( "read", 5 )) ) /tr(( "eval", 3 ));
+    /// assert_eq!( tree.to_folded( |&(label, count)| (label, count) ), "main;parse;read 5\nmain;eval 3\n" );
+    /// ```
+    pub fn to_folded<L,F>( &self, mut label_and_weight: F ) -> String
+        where L: Display
+            , F: FnMut( &T ) -> (L, u64)
+    {
+        use fmt::Write;
+
+        let mut folded = String::new();
+        let mut labels = Vec::<String>::new();
+        for (depth, node) in self.iter_depth() {
+            let (label, weight) = label_and_weight( node.data() );
+            labels.truncate( depth );
+            labels.push( label.to_string() );
+            if node.has_no_child() {
+                for (nth, label) in labels.iter().enumerate() {
+                    if nth != 0 {
+                        folded.push( ';' );
+                    }
+                    folded.push_str( label );
+                }
+                writeln!( folded, " {}", weight ).unwrap();
+            }
+        }
+        folded
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use crate::rust::*;
+    use crate::tr;
+
+    #[test] fn to_folded() {
+        let tree = tr(( "root".to_owned(), 1 ))
+            /( tr(( "child".to_owned(), 5 )) )
+            /( tr(( "a".to_owned(), 0 )) /( tr(( "b".to_owned(), 0 ))/tr(( "c".to_owned(), 7 )) ) /tr(( "d".to_owned(), 2 )) );
+        assert_eq!( tree.to_folded( |(label, count)| (label.clone(), *count) ),
+            "root;child 5\nroot;a;b;c 7\nroot;a;d 2\n" );
+        assert_eq!( tr(( "root", 4 )).to_folded( |&(label, count)| (label, count) ), "root 4\n" );
+        assert_eq!( ( tr(1)/tr(2)/tr(3) ).to_folded( |&data| (data, data as u64 * 10) ), "1;2 20\n1;3 30\n" );
+    }
+}
@@ -11,13 +11,28 @@
 //! 4. `fr()`,`-`,`/` notations for construction.
 
 use super::heap;
-use super::{Tree, Node, Data, Iter, IterMut};
+use super::{BfsMut, Cursor, Tree, Node, Data, Error, Iter, IterMut};
+use super::node::CanonicalIds;
 use super::NodeVec;
 use crate::{Size, TupleForest};
 
 use crate::rust::*;
 
 /// List of `Node`s as its children.
+///
+/// The alternate format `{:#}` displays one node per line, indented by two spaces per level.
+/// The formatter's width and fill, if given, replace the default indentation.
+///
+/// # Examples
+///
+/// ```
+/// use trees::{fr, tr};
+///
+/// let forest = -( tr(1)/tr(2) ) -tr(3);
+/// assert_eq!( format!( "{}", forest ), "( 1( 2 ) 3 )" );
+/// assert_eq!( format!( "{:#}", forest ), "1\n  2\n3" );
+/// assert_eq!( format!( "{:#}", fr::<i32>() ), "" );
+/// ```
 pub struct Forest<T> {
     root : NonNull<Node<T>>,
     mark : PhantomData<Node<T>>,
@@ -53,6 +68,37 @@ impl<T> Forest<T> {
         Forest::from_node( unsafe{ node_vec.as_ref().non_null_node(0) })
     }
 
+    /// Constructs forest from `(depth, data)` pairs listed in pre-order, the roots of the top-level trees being of depth 0,
+    /// and allocates all the nodes at once in one contiguous buffer, as `from_tuple()` does, instead of one allocation per node.
+    ///
+    /// The buffer is freed when the last of its nodes is dropped, so nodes detached from the forest keep it alive.
+    /// Returns `None` if the first pair is not of depth 0, or if the depth increases by more than 1 from one pair to the next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Forest, fr, tr};
+    ///
+    /// let forest = Forest::from_depths_in_arena( vec![ (0,1), (1,2), (0,3), (1,4), (2,5) ]);
+    /// assert_eq!( forest, Some( -( tr(1)/tr(2) ) -( tr(3)/( tr(4)/tr(5) )) ));
+    ///
+    /// assert_eq!( Forest::<i32>::from_depths_in_arena( vec![] ), Some( fr() ));
+    /// assert_eq!( Forest::from_depths_in_arena( vec![ (1,1) ]), None );
+    /// assert_eq!( Forest::from_depths_in_arena( vec![ (0,1), (2,2) ]), None );
+    /// ```
+    pub fn from_depths_in_arena<I>( iter: I ) -> Option<Forest<T>>
+        where I: IntoIterator<Item=(usize,T)>
+    {
+        let pairs = iter.into_iter().collect::<Vec<_>>();
+        if pairs.first().map_or( 0, |pair| pair.0 ) != 0 || pairs.windows(2).any( |w| w[1].0 > w[0].0+1 ) {
+            return None;
+        }
+        let mut node_vec = NodeVec::with_capacity_raw_non_null( pairs.len()+1 );
+        unsafe{ node_vec.as_mut().construct_from_depths( pairs, true )};
+
+        Some( Forest::from_node( unsafe{ node_vec.as_ref().non_null_node(0) }))
+    }
+
     pub(crate) fn from_node( root: NonNull<Node<T>> ) -> Forest<T> {
         Forest{ root, mark: PhantomData }
     }
@@ -125,6 +171,20 @@ impl<T> Forest<T> {
     /// ```
     pub fn iter<'a, 's:'a>( &'s self ) -> Iter<'a,T> { self.root_().iter() }
 
+    /// Provides an iterator over child `Node`s from the last one to the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1)-tr(2)-tr(3);
+    /// assert_eq!( forest.children_rev().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 3, 2, 1 ]);
+    /// ```
+    pub fn children_rev( &self ) -> impl Iterator<Item=&Node<T>> + '_ {
+        iter::successors( self.back(), |node| node.prev.map( |prev| unsafe{ &*prev.as_ptr() }))
+    }
+
     /// Provides a forward iterator over child `Node`s with mutable references.
     ///
     /// # Examples
@@ -141,6 +201,307 @@ impl<T> Forest<T> {
     /// ```
     pub fn iter_mut<'a, 's:'a>( &'s mut self ) -> IterMut<'a,T> { self.root_mut_().iter_mut() }
 
+    /// Provides a mutable iterator over the data of all nodes in level order, each paired with its level,
+    /// the top-level nodes being at level 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -( tr(0)/tr(0) ) -tr(0);
+    /// forest.iter_mut_bfs().for_each( |(level, data)| *data = level );
+    /// assert_eq!( forest.to_string(), "( 0( 1 ) 0 )" );
+    /// ```
+    pub fn iter_mut_bfs( &mut self ) -> BfsMut<'_,T> { BfsMut::new( self.root_().head, self.node_count() )}
+
+    /// Returns `true` if any child `Node` of the forest is associated with the given data.
+    ///
+    /// Only the immediate children are looked at, not their descendants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1)-tr(2)/tr(4)-tr(3);
+    /// assert!( forest.contains( &2 ));
+    /// assert!( !forest.contains( &4 ));
+    /// ```
+    pub fn contains( &self, value: &T ) -> bool
+        where T: PartialEq
+    {
+        self.iter().any( |child| child.data() == value )
+    }
+
+    /// Returns the index of the first child `Node` of the forest associated with the given data,
+    /// or `None` if there is no such child.
+    ///
+    /// Only the immediate children are looked at, not their descendants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1)-tr(2)/tr(4)-tr(3);
+    /// assert_eq!( forest.position( &3 ), Some(2) );
+    /// assert_eq!( forest.position( &4 ), None );
+    /// ```
+    pub fn position( &self, value: &T ) -> Option<usize>
+        where T: PartialEq
+    {
+        self.iter().position( |child| child.data() == value )
+    }
+
+    /// Returns the number of nodes in the whole forest whose data satisfy the predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -( tr(1)/tr(2)/tr(4) ) -tr(6);
+    /// assert_eq!( forest.count_where( |x| x % 2 == 0 ), 3 );
+    /// ```
+    pub fn count_where<F>( &self, mut pred: F ) -> usize
+        where F: FnMut( &T ) -> bool
+    {
+        self.iter()
+            .flat_map( |child| Some( child ).into_iter().chain( child.iter_descendants() ))
+            .filter( |node| pred( node.data() ))
+            .count()
+    }
+
+    /// Folds the children from front to back with a fallible closure, stopping at the first error and returning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1) -( tr(2)/tr(3) ) -tr(4);
+    /// assert_eq!( forest.try_fold_trees( 0, |sum, node| Ok::<_,()>( sum + node.node_count() )), Ok(4) );
+    /// assert_eq!( forest.try_fold_trees( 0, |sum, node| if node.has_no_child() { Ok( sum+1 )} else { Err( sum )}), Err(1) );
+    /// ```
+    pub fn try_fold_trees<B,E,F>( &self, init: B, f: F ) -> Result<B,E>
+        where F: FnMut( B, &Node<T> ) -> Result<B,E>
+    {
+        self.iter().try_fold( init, f )
+    }
+
+    /// Creates a structurally identical forest by calling the closure on each node's data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1)-tr(2);
+    /// assert_eq!( forest.map( |x| x*2 ).to_string(), "( 2 4 )" );
+    /// ```
+    pub fn map<U,F>( &self, mut f: F ) -> Forest<U>
+        where F: FnMut( &T ) -> U
+    {
+        let mut forest = Forest::new();
+        for node in self.iter() {
+            match node.try_map_subtree( |data| Ok::<U,Infallible>( f( data ))) {
+                Ok( tree ) => forest.push_back( tree ),
+                Err( never ) => match never {},
+            }
+        }
+        forest
+    }
+
+    /// Creates a new forest of clones of the top-level trees whose roots satisfy the predicate,
+    /// leaving this forest unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1)-tr(2)-tr(3)-tr(4);
+    /// assert_eq!( forest.filter_trees( |node| node.data() % 2 == 0 ).to_string(), "( 2 4 )" );
+    /// assert_eq!( forest.to_string(), "( 1 2 3 4 )" );
+    /// ```
+    pub fn filter_trees<F>( &self, mut pred: F ) -> Forest<T>
+        where T: Clone, F: FnMut( &Node<T> ) -> bool
+    {
+        let mut forest = Forest::new();
+        forest.concat( self.iter().filter( |node| pred( node )).map( |node| node.deep_clone() ));
+        forest
+    }
+
+    /// Provides an iterator over runs of adjacent children, where `same` holds for each pair of neighbours in a run.
+    /// Like `slice::chunk_by()`, each run is as long as possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1)-tr(1)-tr(2)-tr(1);
+    /// let runs = forest.chunks_by( |a,b| a.data() == b.data() )
+    ///     .map( |run| run.iter().map( |node| *node.data() ).collect::<Vec<_>>() )
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!( runs, vec![ vec![1,1], vec![2], vec![1] ]);
+    /// ```
+    pub fn chunks_by<'a,F>( &'a self, mut same: F ) -> impl Iterator<Item=Vec<&'a Node<T>>> + 'a
+        where F: FnMut( &Node<T>, &Node<T> ) -> bool + 'a
+    {
+        let mut children = self.iter().peekable();
+        iter::from_fn( move || {
+            let mut run = vec![ children.next()? ];
+            while let Some( next ) = children.next_if( |next| same( run[ run.len()-1 ], next )) {
+                run.push( next );
+            }
+            Some( run )
+        })
+    }
+
+    /// Returns `true` if both forests are equal when the order of children is ignored at every level,
+    /// i.e. children are compared as multisets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -( tr(1)/tr(2)/tr(3) ) -tr(1);
+    /// assert!( forest.eq_unordered( &( -tr(1) -( tr(1)/tr(3)/tr(2) ))));
+    /// assert!( !forest.eq_unordered( &( -tr(1) -( tr(1)/tr(3) ))));
+    /// ```
+    pub fn eq_unordered( &self, other: &Forest<T> ) -> bool
+        where T: Ord
+    {
+        if self.node_count() != other.node_count() {
+            return false;
+        }
+        let mut ids = CanonicalIds::new();
+        let mut a = self.iter().map( |node| node.canonical_id( &mut ids )).collect::<Vec<_>>();
+        let mut b = other.iter().map( |node| node.canonical_id( &mut ids )).collect::<Vec<_>>();
+        a.sort_unstable();
+        b.sort_unstable();
+        a == b
+    }
+
+    /// Formats the forest like `Display` does, but with custom brackets around children and separator between siblings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{fr, tr};
+    ///
+    /// let forest = -( tr(1)/tr(2)/tr(3) ) -tr(4);
+    /// assert_eq!( forest.format_with( "( ", " )", " " ), "( 1( 2 3 ) 4 )" );
+    /// assert_eq!( forest.format_with( "(", ")", " " ), "(1(2 3) 4)" );
+    /// assert_eq!( fr::<i32>().format_with( "[", "]", ", " ), "[]" );
+    /// assert_eq!( fr::<i32>().format_with( "( ", " )", " " ), "()" );
+    /// ```
+    pub fn format_with( &self, open: &str, close: &str, sep: &str ) -> String
+        where T: Display
+    {
+        if self.has_no_child() {
+            // Like `Display` writing "()", the padding inside the brackets is dropped.
+            let mut s = String::from( open.trim_end() );
+            s.push_str( close.trim_start() );
+            s
+        } else {
+            use fmt::Write;
+
+            let mut s = String::new();
+            self.root_().format_children_with( &mut s, open, close, sep, &mut |s, data| write!( s, "{}", data ).unwrap() );
+            s
+        }
+    }
+
+    /// Formats the forest like `Display` does, but quotes the data that could not be parsed back otherwise,
+    /// as `Node::to_string_escaped()` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{fr, tr};
+    ///
+    /// let forest = -( tr( "hello (world)" )/tr( "a b" ) ) -tr( r#""quoted""# );
+    /// assert_eq!( forest.to_string_escaped(), r#"( "hello (world)"( "a b" ) "\"quoted\"" )"# );
+    /// assert_eq!( fr::<i32>().to_string_escaped(), "()" );
+    /// ```
+    pub fn to_string_escaped( &self ) -> String
+        where T: Display
+    {
+        if self.has_no_child() {
+            String::from( "()" )
+        } else {
+            let mut s = String::new();
+            self.root_().format_children_with( &mut s, "( ", " )", " ", &mut crate::node::write_escaped );
+            s
+        }
+    }
+
+    /// Compares the node counts of two forests first, and then the forests themselves with `Ord::cmp()` if the counts are equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!( ( -tr(9)-tr(9) ).cmp_by_size( &( -tr(1)-tr(1)-tr(1) )), Ordering::Less );
+    /// assert_eq!( ( -tr(1)-tr(2) ).cmp_by_size( &( -tr(1)-tr(1) )), Ordering::Greater );
+    /// ```
+    pub fn cmp_by_size( &self, other: &Self ) -> Ordering
+        where T: Ord
+    {
+        self.node_count().cmp( &other.node_count() ).then_with( || self.cmp( other ))
+    }
+
+    /// Walks the whole forest and checks its internal links and cached sizes,
+    /// returning an error describing the first inconsistency found.
+    ///
+    /// Forests built by this crate's safe APIs always pass.
+    /// It is intended for debugging code which manipulates nodes through `unsafe` internals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let forest = -( tr(1)/tr(2) ) -tr(3);
+    /// assert!( forest.validate().is_ok() );
+    /// ```
+    pub fn validate( &self ) -> Result<(), Error> { self.root_().validate() }
+
+    /// Returns `true` if both forests have the same trees regardless of their order,
+    /// i.e. the trees are compared as a multiset while each tree is compared with `==`.
+    ///
+    /// Unlike [`eq_unordered`](#method.eq_unordered), the order of children below the top level matters.
+    /// It takes quadratic time in the degree, comparing each tree with the unmatched ones of the same size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// assert!( ( -tr(1)-( tr(2)/tr(3)/tr(4) ) ).eq_roots_unordered( &( -( tr(2)/tr(3)/tr(4) )-tr(1) )));
+    /// assert!( !( -tr(1)-( tr(2)/tr(3)/tr(4) ) ).eq_roots_unordered( &( -( tr(2)/tr(4)/tr(3) )-tr(1) )));
+    /// ```
+    pub fn eq_roots_unordered( &self, other: &Forest<T> ) -> bool
+        where T: Eq
+    {
+        if self.degree() != other.degree() || self.node_count() != other.node_count() {
+            return false;
+        }
+        let mut unmatched = other.iter().collect::<Vec<_>>();
+        self.iter().all( |node| {
+            match unmatched.iter().position( |&other| other.node_count() == node.node_count() && other == node ) {
+                Some( index ) => { unmatched.swap_remove( index ); true },
+                None => false,
+            }
+        })
+    }
+
     /// Returns the first child of the forest,
     /// or `None` if it is empty.
     pub fn front( &self ) -> Option<&Node<T>> {
@@ -163,6 +524,24 @@ impl<T> Forest<T> {
         self.root_mut_().back_mut()
     }
 
+    /// Provides a cursor pointing to the first child, or to the ghost position if the forest is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut forest = -tr(1)-tr(2);
+    /// let mut cursor = forest.cursor_front();
+    /// assert_eq!( cursor.current().map( |node| *node.data() ), Some(1) );
+    /// cursor.insert_after( tr(3) );
+    /// assert_eq!( forest.to_string(), "( 1 3 2 )" );
+    /// ```
+    pub fn cursor_front( &mut self ) -> Cursor<'_,T> {
+        let head = self.root_().head;
+        Cursor::new( self, head )
+    }
+
     /// Add the tree as the first child.
     ///
     /// # Examples
@@ -270,63 +649,1118 @@ impl<T> Forest<T> {
     pub fn append( &mut self, forest: Forest<T> ) {
         self.root_mut_().append( forest );
     }
-}
-
-impl<T> Default for Forest<T> { fn default() -> Self { Forest::new() }}
 
-impl<T> Drop for Forest<T> {
-    fn drop( &mut self ) {
-        while let Some(_) = self.pop_front() {}
-        heap::drop_node( self.root );
+    /// Inserts all the forest's trees into children list, the first of which at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > degree()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2)-tr(3);
+    /// forest.splice( 1, -tr(7)-tr(8) );
+    /// assert_eq!( forest.to_string(), "( 1 7 8 2 3 )" );
+    /// ```
+    pub fn splice( &mut self, index: usize, mut forest: Forest<T> ) {
+        let degree = self.degree();
+        assert!( index <= degree, "splice index {} out of bounds for degree {}", index, degree );
+        if index == 0 {
+            self.prepend( forest );
+        } else if index == degree {
+            self.append( forest );
+        } else if !forest.has_no_child() {
+            let mut prev = self.root_().locate( &[ index-1 ]).unwrap();
+            let root = self.root_mut_();
+            forest.set_up( root );
+            unsafe {
+                let mut next = prev.as_ref().next.unwrap();
+                prev.as_mut().connect_next( forest.root_mut_().head.unwrap().as_mut() );
+                forest.root_mut_().tail.unwrap().as_mut().connect_next( next.as_mut() );
+            }
+            let size = forest.root_().size;
+            root.inc_sizes( size.degree, size.descendants );
+            forest.clear();
+        }
     }
-}
 
-impl<T:Clone> Clone for Forest<T> {
-    fn clone( &self ) -> Self {
-        self.root_().deep_clone_forest()
+    /// Splits the forest into two at the given index,
+    /// returning the children from `at` on and keeping the ones before `at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > degree()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2)-tr(3);
+    /// assert_eq!( forest.split_off( 1 ).to_string(), "( 2 3 )" );
+    /// assert_eq!( forest.to_string(), "( 1 )" );
+    /// ```
+    pub fn split_off( &mut self, at: usize ) -> Forest<T> {
+        self.root_mut_().split_off( at )
     }
-}
-
-impl_debug_display_for_forest!( Forest, iter() );
-impl_order_relations_for_collection!( Forest, iter() );
-impl_hash_for_forest!( Forest, iter() );
-
-#[cfg( test )]
-mod tests {
-    use super::*;
 
-    #[test] fn empty_piled_forest_from_tuple() {
-        let tuple = ();
-        let piled = Forest::<i32>::from_tuple( tuple );
-        assert_eq!( piled.to_string(), "()" );
+    /// Consumes two forests, combining their trees pairwise with the closure into a new forest,
+    /// or returns `None` if they have different numbers of trees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let forest = -tr(1)-tr(2);
+    /// let zipped = forest.zip_with( -tr(10)-tr(20), |a, b| tr( a.data() + b.data() ));
+    /// assert_eq!( zipped.unwrap().to_string(), "( 11 22 )" );
+    /// assert_eq!( ( -tr(1) ).zip_with( -tr(2)-tr(3), |a, _| a ), None );
+    /// ```
+    pub fn zip_with<U,V,F>( mut self, mut other: Forest<U>, mut f: F ) -> Option<Forest<V>>
+        where F: FnMut( Tree<T>, Tree<U> ) -> Tree<V>
+    {
+        if self.degree() != other.degree() {
+            return None;
+        }
+        let mut zipped = Forest::new();
+        while let (Some( a ), Some( b )) = (self.pop_front(), other.pop_front()) {
+            zipped.push_back( f( a, b ));
+        }
+        Some( zipped )
     }
 
-    #[test] fn piled_forest_from_tuple() {
-        let tuple = ( (2,3,4), (5,6,7) );
-        let piled = Forest::<i32>::from_tuple( tuple );
-        assert_eq!( piled.to_string(), "( 2( 3 4 ) 5( 6 7 ) )" );
+    /// Returns the root of the tree with the most nodes, the first one if there is a tie, or `None` if the forest is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let forest = -tr(1) -( tr(2)/tr(3)/tr(4) ) -( tr(5)/tr(6) );
+    /// assert_eq!( forest.largest().map( |node| *node.data() ), Some(2) );
+    /// ```
+    pub fn largest( &self ) -> Option<&Node<T>> {
+        self.iter().rev().max_by_key( |node| node.node_count() )
     }
-}
 
-#[cfg( miri )]
-mod miri_tests {
-    #[test] fn has_no_child() {
-        use crate::{fr, tr};
-
-        let mut forest = fr();
-        assert!( forest.has_no_child() );
-        forest.push_back( tr(1) );
-        assert!( !forest.has_no_child() );
+    /// Returns the root of the tree with the fewest nodes, the first one if there is a tie, or `None` if the forest is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let forest = -( tr(1)/tr(2) ) -tr(3) -tr(4);
+    /// assert_eq!( forest.smallest().map( |node| *node.data() ), Some(3) );
+    /// ```
+    pub fn smallest( &self ) -> Option<&Node<T>> {
+        self.iter().min_by_key( |node| node.node_count() )
     }
 
-    #[test] fn degree() {
-        use crate::Forest;
+    /// Merges the trees of `other` into the forest in alternating order, starting with the forest's first tree,
+    /// followed by the remaining trees of the longer one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(3);
+    /// forest.interleave( -tr(2)-tr(4)-tr(6) );
+    /// assert_eq!( forest.to_string(), "( 1 2 3 4 6 )" );
+    /// ```
+    pub fn interleave( &mut self, mut other: Forest<T> ) {
+        let mut mine = self.split_off( 0 );
+        loop {
+            match (mine.pop_front(), other.pop_front()) {
+                (Some( a ), Some( b )) => {
+                    self.push_back( a );
+                    self.push_back( b );
+                },
+                (Some( a ), None) => {
+                    self.push_back( a );
+                    self.append( mine );
+                    break;
+                },
+                (None, Some( b )) => {
+                    self.push_back( b );
+                    self.append( other );
+                    break;
+                },
+                (None, None) => break,
+            }
+        }
+    }
 
-        let forest = Forest::<i32>::from_tuple(( 0, (1,2), (3,4) ));
-        assert_eq!( forest.degree(), 3 );
+    /// Moves all the trees out into a new forest, leaving this one empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-( tr(2)/tr(3) );
+    /// let taken = forest.take();
+    /// assert_eq!( taken.to_string(), "( 1 2( 3 ) )" );
+    /// assert!( forest.has_no_child() );
+    /// ```
+    pub fn take( &mut self ) -> Forest<T> {
+        self.replace( Forest::new() )
     }
 
-    #[test] fn node_count() {
+    /// Replaces all the trees with the ones of `forest`, returning the old ones as a forest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2);
+    /// let old = forest.replace( -tr(3) );
+    /// assert_eq!( old.to_string(), "( 1 2 )" );
+    /// assert_eq!( forest.to_string(), "( 3 )" );
+    /// ```
+    pub fn replace( &mut self, forest: Forest<T> ) -> Forest<T> {
+        mem::replace( self, forest )
+    }
+
+    /// Consumes the forest, returning its first tree and a forest of the remaining ones, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{fr, tr};
+    /// let forest = -tr(1)-tr(2)-tr(3);
+    /// let (first, rest) = forest.split_first().unwrap();
+    /// assert_eq!( first, tr(1) );
+    /// assert_eq!( rest.to_string(), "( 2 3 )" );
+    /// assert_eq!( fr::<i32>().split_first(), None );
+    /// ```
+    pub fn split_first( mut self ) -> Option<(Tree<T>, Forest<T>)> {
+        self.pop_front().map( |first| (first, self) )
+    }
+
+    /// Removes the `index`-th tree, putting its root's children in its place in order, and returns the root's data,
+    /// or returns `None` if there is no such tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1) -( tr(2)/tr(3)/tr(4) ) -tr(5);
+    /// assert_eq!( forest.dissolve_child(1), Some(2) );
+    /// assert_eq!( forest.to_string(), "( 1 3 4 5 )" );
+    /// ```
+    pub fn dissolve_child( &mut self, index: usize ) -> Option<T> {
+        self.root_mut_().dissolve_child( index )
+    }
+
+    /// Consumes the forest, moving the children satisfying the predicate into the first forest returned
+    /// and the others into the second one, preserving their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let forest = -tr(1)-tr(2)-tr(3)-tr(4);
+    /// let (even, odd) = forest.partition( |node| node.data() % 2 == 0 );
+    /// assert_eq!( even.to_string(), "( 2 4 )" );
+    /// assert_eq!( odd.to_string(), "( 1 3 )" );
+    /// ```
+    pub fn partition<F>( mut self, mut pred: F ) -> (Forest<T>, Forest<T>)
+        where F: FnMut( &Node<T> ) -> bool
+    {
+        let mut matching = Forest::new();
+        let mut non_matching = Forest::new();
+        while let Some( tree ) = self.pop_front() {
+            if pred( tree.root() ) {
+                matching.push_back( tree );
+            } else {
+                non_matching.push_back( tree );
+            }
+        }
+        (matching, non_matching)
+    }
+
+    /// Consumes the forest, grouping its children by the key returned by the closure under new parent nodes,
+    /// whose data is converted from the keys, and returns the tree of `root` with these parents as its children.
+    ///
+    /// The groups are in the order of the first appearances of their keys,
+    /// and the children of each group are in their original order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let forest = -tr("1")-tr("2")-tr("3")-tr("4");
+    /// let tree = forest.group_into_tree( "r", |node| if node.data().parse::<i32>().unwrap() % 2 == 0 { "even" } else { "odd" });
+    /// assert_eq!( tree.to_string(), "r( odd( 1 3 ) even( 2 4 ) )" );
+    /// ```
+    pub fn group_into_tree<K,F>( mut self, root: T, mut key: F ) -> Tree<T>
+        where K: PartialEq + Into<T>
+            , F: FnMut( &Node<T> ) -> K
+    {
+        let mut groups = Vec::<(K, Forest<T>)>::new();
+        while let Some( tree ) = self.pop_front() {
+            let k = key( tree.root() );
+            match groups.iter_mut().find( |(group_key, _)| *group_key == k ) {
+                Some(( _, group )) => group.push_back( tree ),
+                None => {
+                    let mut group = Forest::new();
+                    group.push_back( tree );
+                    groups.push(( k, group ));
+                },
+            }
+        }
+        Tree::with_children( root, groups.into_iter().map( |(k, group)| Tree::from_forest( k.into(), group )))
+    }
+
+    /// Consumes the forest, replacing each child with the forest returned by the closure
+    /// and concatenating the results in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let forest = -tr(1)-tr(2);
+    /// let doubled = forest.flat_map( |tree| -tree.clone()-tree );
+    /// assert_eq!( doubled.to_string(), "( 1 1 2 2 )" );
+    /// ```
+    pub fn flat_map<F>( mut self, mut f: F ) -> Forest<T>
+        where F: FnMut( Tree<T> ) -> Forest<T>
+    {
+        let mut forest = Forest::new();
+        while let Some( tree ) = self.pop_front() {
+            forest.append( f( tree ));
+        }
+        forest
+    }
+
+    /// Swaps the children at indices `i` and `j`, together with their descendants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2)-tr(3);
+    /// forest.swap( 0, 2 );
+    /// assert_eq!( forest.to_string(), "( 3 2 1 )" );
+    /// ```
+    pub fn swap( &mut self, i: usize, j: usize ) {
+        let degree = self.degree();
+        assert!( i < degree && j < degree, "swap indices ({}, {}) out of bounds for degree {}", i, j, degree );
+        if i != j {
+            let x = self.root_().locate( &[i] ).unwrap();
+            let y = self.root_().locate( &[j] ).unwrap();
+            unsafe{ Node::swap_positions( x, y ); }
+        }
+    }
+
+    /// Rotates the children in place such that the child at `mid` becomes the first one,
+    /// the same as `slice::rotate_left()`. Only the links at both ends are changed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > degree()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2)-tr(3);
+    /// forest.rotate_left( 1 );
+    /// assert_eq!( forest.to_string(), "( 2 3 1 )" );
+    /// ```
+    pub fn rotate_left( &mut self, mid: usize ) {
+        let degree = self.degree();
+        assert!( mid <= degree, "rotation {} out of bounds for degree {}", mid, degree );
+        if mid != 0 && mid != degree {
+            let mut new_head = self.root_().locate( &[mid] ).unwrap();
+            let root = self.root_mut_();
+            unsafe {
+                let mut new_tail = new_head.as_ref().prev.unwrap();
+                root.tail.unwrap().as_mut().connect_next( root.head.unwrap().as_mut() );
+                new_tail.as_mut().next = None;
+                new_head.as_mut().prev = None;
+                root.head = Some( new_head );
+                root.tail = Some( new_tail );
+            }
+        }
+    }
+
+    /// Rotates the children in place such that the last `k` children become the first ones,
+    /// the same as `slice::rotate_right()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k > degree()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2)-tr(3);
+    /// forest.rotate_right( 1 );
+    /// assert_eq!( forest.to_string(), "( 3 1 2 )" );
+    /// ```
+    pub fn rotate_right( &mut self, k: usize ) {
+        let degree = self.degree();
+        assert!( k <= degree, "rotation {} out of bounds for degree {}", k, degree );
+        self.rotate_left( degree - k );
+    }
+
+    /// Add all the iterator's trees at back of children list, the same as calling `push_back()` on each of them.
+    ///
+    /// The trees are linked to each other first and then attached as a whole,
+    /// so the sizes are updated only once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Forest, tr};
+    /// let mut forest = Forest::new();
+    /// forest.push_back( tr(1) );
+    /// forest.concat( vec![ tr(2), tr(3)/tr(4) ]);
+    /// assert_eq!( forest.to_string(), "( 1 2 3( 4 ) )" );
+    /// ```
+    pub fn concat<I>( &mut self, iter: I ) where I: IntoIterator<Item=Tree<T>> {
+        let root = self.root;
+        let mut head: Option<NonNull<Node<T>>> = None;
+        let mut tail: Option<NonNull<Node<T>>> = None;
+        let mut degree = 0;
+        let mut node_cnt = 0;
+        for mut tree in iter {
+            let node = tree.root_mut_();
+            node.set_up( unsafe{ root.as_ref() });
+            match tail {
+                Some( mut tail ) => unsafe{ tail.as_mut().connect_next( node )},
+                None => head = Some( node.non_null() ),
+            }
+            tail = Some( node.non_null() );
+            degree += 1;
+            node_cnt += node.node_count();
+            mem::forget( tree );
+        }
+        if let (Some( mut head ), Some( tail )) = (head, tail) {
+            let root = self.root_mut_();
+            match root.tail {
+                Some( mut old_tail ) => unsafe{ old_tail.as_mut().connect_next( head.as_mut() )},
+                None => root.head = Some( head ),
+            }
+            root.tail = Some( tail );
+            root.inc_sizes( degree, node_cnt );
+        }
+    }
+
+    /// Removes consecutive children whose root nodes resolve to the same key, keeping the first of each run.
+    /// Like `Vec::dedup_by_key()`, only adjacent duplicates are removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(1)-tr(2)-tr(2)-tr(1);
+    /// forest.dedup_by_key( |node| *node.data() );
+    /// assert_eq!( forest.to_string(), "( 1 2 1 )" );
+    /// ```
+    pub fn dedup_by_key<K,F>( &mut self, mut key: F )
+        where K: PartialEq
+            , F: FnMut( &Node<T> ) -> K
+    {
+        let mut cursor = self.cursor_front();
+        let mut prev = None;
+        while let Some( node ) = cursor.current() {
+            let curr = key( node );
+            if prev.as_ref() == Some( &curr ) {
+                cursor.remove_current();
+            } else {
+                prev = Some( curr );
+                cursor.move_next();
+            }
+        }
+    }
+
+    /// Drops all the children after the first `len` ones, together with their descendants.
+    /// Does nothing if `len >= degree()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// let mut forest = -tr(1)-tr(2)-tr(3)-tr(4);
+    /// forest.truncate( 2 );
+    /// assert_eq!( forest.to_string(), "( 1 2 )" );
+    /// ```
+    pub fn truncate( &mut self, len: usize ) {
+        while self.degree() > len {
+            self.pop_back();
+        }
+    }
+
+    /// Add all the iterator's trees at front of children list, preserving their order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Forest, tr};
+    /// let mut forest = Forest::new();
+    /// forest.push_back( tr(9) );
+    /// forest.extend_front( vec![ tr(1), tr(2) ]);
+    /// assert_eq!( forest.to_string(), "( 1 2 9 )" );
+    /// ```
+    pub fn extend_front<I>( &mut self, iter: I ) where I: IntoIterator<Item=Tree<T>> {
+        let mut forest = Forest::new();
+        iter.into_iter().for_each( |tree| forest.push_back( tree ));
+        self.prepend( forest );
+    }
+}
+
+impl<T> Default for Forest<T> { fn default() -> Self { Forest::new() }}
+
+impl<T> Drop for Forest<T> {
+    fn drop( &mut self ) {
+        while let Some(_) = self.pop_front() {}
+        heap::drop_node( self.root );
+    }
+}
+
+impl<T:Clone> Clone for Forest<T> {
+    fn clone( &self ) -> Self {
+        self.root_().deep_clone_forest()
+    }
+}
+
+impl_debug_display_for_forest!( Forest, iter() );
+impl_order_relations_for_collection!( Forest, iter() );
+impl_hash_for_forest!( Forest, iter() );
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test] fn from_depths_in_arena() {
+        use crate::tr;
+
+        let dropped = Cell::new( 0 );
+        #[derive( Debug, PartialEq )] struct Counted<'a>( i32, &'a Cell<usize> );
+        impl Drop for Counted<'_> { fn drop( &mut self ) { self.1.set( self.1.get() + 1 ); }}
+
+        let pairs = [ (0,1), (1,2), (2,3), (1,4), (0,5), (0,6), (1,7) ];
+        let mut forest = Forest::from_depths_in_arena( pairs.iter().map( |&(depth, data)| (depth, Counted( data, &dropped )))).unwrap();
+        assert_eq!( forest.iter().map( |node| node.node_count() ).collect::<Vec<_>>(), vec![ 4, 1, 2 ]);
+        assert!( forest.validate().is_ok() );
+        assert_eq!( forest.node_count(), 7 );
+        assert_eq!( forest.iter().map( |node| node.data().0 ).collect::<Vec<_>>(), vec![ 1, 5, 6 ]);
+
+        let mut first = forest.pop_front().unwrap();
+        let detached = first.root_mut().front_mut().unwrap().detach();
+        drop( forest );
+        drop( first );
+        assert_eq!( dropped.get(), 5 );
+        assert_eq!( detached.root().data().0, 2 );
+        assert_eq!( detached.node_count(), 2 );
+        assert!( detached.validate().is_ok() );
+        drop( detached );
+        assert_eq!( dropped.get(), 7 );
+
+        let pairs = ( tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4) ).iter_depth().map( |(depth, node)| (depth, *node.data()) ).collect::<Vec<_>>();
+        let mut forest = Forest::from_depths_in_arena( pairs ).unwrap();
+        forest.push_back( tr(5) );
+        forest.front_mut().unwrap().push_front( tr(6) );
+        assert_eq!( forest.to_string(), "( 0( 6 1( 2 3 ) 4 ) 5 )" );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] fn empty_piled_forest_from_tuple() {
+        let tuple = ();
+        let piled = Forest::<i32>::from_tuple( tuple );
+        assert_eq!( piled.to_string(), "()" );
+    }
+
+    #[test] fn piled_forest_from_tuple() {
+        let tuple = ( (2,3,4), (5,6,7) );
+        let piled = Forest::<i32>::from_tuple( tuple );
+        assert_eq!( piled.to_string(), "( 2( 3 4 ) 5( 6 7 ) )" );
+    }
+
+    #[test] fn rotate() {
+        use crate::{fr, tr};
+
+        for degree in 0..5 {
+            for mid in 0..=degree {
+                let mut forest = fr();
+                (0..degree).for_each( |i| forest.push_back( tr(i)/tr(i*10) ));
+                let mut expected = (0..degree).collect::<Vec<_>>();
+
+                forest.rotate_left( mid );
+                expected.rotate_left( mid );
+                assert_eq!( forest.iter().map( |node| *node.data() ).collect::<Vec<_>>(), expected );
+                assert!( forest.validate().is_ok() );
+
+                forest.rotate_right( mid );
+                expected.rotate_right( mid );
+                assert_eq!( forest.iter().map( |node| *node.data() ).collect::<Vec<_>>(), expected );
+                assert_eq!( forest.node_count(), degree*2 );
+                assert!( forest.validate().is_ok() );
+            }
+        }
+    }
+
+    #[test] #[should_panic] fn rotate_out_of_bounds() {
+        use crate::tr;
+
+        let mut forest = -tr(1)-tr(2);
+        forest.rotate_left( 3 );
+    }
+
+    #[test] fn swap_children() {
+        use crate::tr;
+
+        let mut forest = -tr(1)-( tr(2)/tr(4)/tr(5) )-tr(3);
+        forest.swap( 1, 1 );
+        assert_eq!( forest.to_string(), "( 1 2( 4 5 ) 3 )" );
+        forest.swap( 2, 1 );
+        assert_eq!( forest.to_string(), "( 1 3 2( 4 5 ) )" );
+        forest.swap( 0, 2 );
+        assert_eq!( forest.to_string(), "( 2( 4 5 ) 3 1 )" );
+        assert_eq!( forest.front().unwrap().node_count(), 3 );
+        assert_eq!( forest.back().unwrap().data(), &1 );
+        assert_eq!( forest.node_count(), 5 );
+    }
+
+    #[test] fn clone_deep_forest() {
+        use crate::{Forest, Tree, tr};
+
+        let mut deepest = Tree::new( 100_000 );
+        for depth in (0..100_000).rev() {
+            let mut tree = Tree::new( depth );
+            tree.push_back( deepest );
+            deepest = tree;
+        }
+        let mut forest = Forest::new();
+        forest.push_back( tr(-1) );
+        forest.push_back( deepest );
+        let cloned = forest.clone();
+        assert_eq!( cloned.node_count(), 100_002 );
+        assert!( cloned == forest );
+
+        forest.back_mut().unwrap().push_back( tr(0) );
+        assert!( cloned != forest );
+    }
+
+    #[test] fn splice() {
+        use crate::{fr, tr};
+
+        let mut forest = -tr(1)-tr(2)-tr(3);
+        forest.splice( 1, -tr(7)-tr(8) );
+        assert_eq!( forest.to_string(), "( 1 7 8 2 3 )" );
+        forest.splice( 0, -tr(0) );
+        forest.splice( 6, -( tr(9)/tr(10) ) );
+        forest.splice( 3, fr() );
+        assert_eq!( forest.to_string(), "( 0 1 7 8 2 3 9( 10 ) )" );
+        assert_eq!( forest.degree(), 7 );
+        assert_eq!( forest.node_count(), 8 );
+        assert!( forest.iter().all( |child| child.parent().is_none() ));
+        assert_eq!( forest.into_iter().rev().map( |tree| *tree.data() ).collect::<Vec<_>>(), vec![ 9, 3, 2, 8, 7, 1, 0 ]);
+    }
+
+    #[test] #[should_panic] fn splice_out_of_bounds() {
+        use crate::tr;
+
+        let mut forest = -tr(1)-tr(2);
+        forest.splice( 3, -tr(3) );
+    }
+
+    #[test] fn concat() {
+        use crate::tr;
+
+        let trees = || (0..1000).map( |i| if i % 3 == 0 { tr(i) /tr(-i) } else { tr(i) });
+
+        let mut pushed = -tr(-1);
+        trees().for_each( |tree| pushed.push_back( tree ));
+        let mut concated = -tr(-1);
+        concated.concat( trees() );
+        assert_eq!( concated, pushed );
+        assert_eq!( concated.degree(), 1001 );
+        assert_eq!( concated.node_count(), pushed.node_count() );
+        assert_eq!( concated.back().map( |node| *node.data() ), Some(999) );
+        assert_eq!( concated.pop_back(), Some( tr(999) /tr(-999) ));
+        assert_eq!( concated.pop_back(), Some( tr(998) ));
+        assert_eq!( concated.back().map( |node| *node.data() ), Some(997) );
+
+        let mut empty = Forest::new();
+        empty.concat( trees() );
+        assert_eq!( empty.iter().zip( pushed.iter().skip(1) ).filter( |(a,b)| a != b ).count(), 0 );
+        empty.concat( None );
+        assert_eq!( empty.degree(), 1000 );
+        assert!( empty.iter().all( |node| node.parent().is_none() ));
+    }
+
+    #[test] fn format_with() {
+        use crate::tr;
+
+        let forest = -( tr(1)/( tr(2)/tr(3)/tr(4) )/tr(5) ) -tr(6) -( tr(7)/tr(8) );
+        assert_eq!( forest.format_with( "[", "]", ", " ), "[1[2[3, 4], 5], 6, 7[8]]" );
+        assert_eq!( forest.format_with( " {", "}", "" ), " {1 {2 {34}5}67 {8}}" );
+        assert_eq!( forest.format_with( "<", ">", "|" ), "<1<2<3|4>|5>|6|7<8>>" );
+        assert_eq!( forest.format_with( "( ", " )", " " ), forest.to_string() );
+
+        let empty = Forest::<i32>::new();
+        assert_eq!( empty.format_with( "( ", " )", " " ), empty.to_string() );
+        assert_eq!( empty.format_with( "[ ", " ]", ", " ), "[]" );
+        assert_eq!( empty.format_with( "<", ">", "|" ), "<>" );
+    }
+
+    #[test] fn validate() {
+        use crate::tr;
+
+        let mut forest = -tr(1) -( tr(2)/tr(3) ) -tr(4);
+        assert!( forest.validate().is_ok() );
+        {
+            let root = forest.root_mut_();
+            let tail = root.tail;
+            root.tail = root.head;
+            assert!( forest.validate().is_err() );
+            forest.root_mut_().tail = tail;
+        }
+        assert!( forest.validate().is_ok() );
+        forest.truncate( 1 );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] fn dedup_by_key() {
+        use crate::tr;
+
+        let mut forest = -tr(1)-tr(1)-tr(2)-tr(2)-tr(1);
+        forest.dedup_by_key( |node| *node.data() );
+        assert_eq!( forest.to_string(), "( 1 2 1 )" );
+
+        let mut forest = -( tr(1)/tr(2) ) -( tr(3)/tr(4) ) -tr(5) -tr(6) -( tr(7)/tr(8)/tr(9) );
+        forest.dedup_by_key( |node| node.degree() );
+        assert_eq!( forest, -( tr(1)/tr(2) ) -tr(5) -( tr(7)/tr(8)/tr(9) ));
+        assert_eq!( forest.node_count(), 6 );
+        assert!( forest.validate().is_ok() );
+
+        let mut forest = Forest::<i32>::new();
+        forest.dedup_by_key( |node| *node.data() );
+        assert!( forest.has_no_child() );
+    }
+
+    #[test] fn cmp_by_size() {
+        use crate::{fr, tr};
+
+        let mut forests = vec![ -( tr(1)/tr(2) ), fr(), -tr(9), -tr(0)-tr(0) ];
+        forests.sort_by( Forest::cmp_by_size );
+        assert_eq!( forests, vec![ fr(), -tr(9), -tr(0)-tr(0), -( tr(1)/tr(2) )]);
+    }
+
+    #[test] fn partition() {
+        use crate::{fr, tr};
+
+        let forest = -tr(1) -( tr(2)/tr(5)/( tr(6)/tr(7) ) ) -tr(3) -tr(4);
+        let (even, odd) = forest.partition( |node| node.data() % 2 == 0 );
+        assert_eq!( even, -( tr(2)/tr(5)/( tr(6)/tr(7) ) ) -tr(4) );
+        assert_eq!( odd, -tr(1)-tr(3) );
+        assert_eq!( even.node_count(), 5 );
+        assert!( even.validate().is_ok() );
+        assert!( odd.validate().is_ok() );
+
+        let (all, none) = ( -tr(1)-tr(2) ).partition( |_| true );
+        assert_eq!( all.to_string(), "( 1 2 )" );
+        assert!( none.has_no_child() );
+        let (left, right) = fr::<i32>().partition( |_| true );
+        assert!( left.has_no_child() && right.has_no_child() );
+    }
+
+    #[test] fn split_off() {
+        use crate::{fr, tr};
+
+        let mut forest = -tr(1)-( tr(2)/tr(3) )-tr(4);
+        assert_eq!( forest.split_off( 3 ), fr() );
+        let mut second = forest.split_off( 1 );
+        assert_eq!( forest, -tr(1) );
+        assert_eq!( second, -( tr(2)/tr(3) )-tr(4) );
+        assert_eq!( second.node_count(), 3 );
+        assert!( forest.validate().is_ok() );
+        assert!( second.validate().is_ok() );
+
+        let third = second.split_off( 0 );
+        assert_eq!( second, fr() );
+        assert_eq!( third.back(), Some( tr(4).root() ));
+        assert!( third.validate().is_ok() );
+        forest.append( third );
+        assert_eq!( forest, -tr(1)-( tr(2)/tr(3) )-tr(4) );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] #[should_panic] fn split_off_out_of_bounds() {
+        use crate::tr;
+
+        let mut forest = -tr(1);
+        forest.split_off( 2 );
+    }
+
+    #[test] fn count_where() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/( tr(2)/tr(3) ) ) -tr(4) -( tr(5)/tr(6)/tr(7) );
+        assert_eq!( forest.count_where( |x| x % 2 == 1 ), 4 );
+        assert_eq!( forest.count_where( |_| true ), forest.node_count() );
+        assert_eq!( fr::<i32>().count_where( |_| true ), 0 );
+    }
+
+    #[test] fn eq_roots_unordered() {
+        use crate::{fr, tr};
+
+        assert!( ( -tr('a')-tr('b') ).eq_roots_unordered( &( -tr('b')-tr('a') )));
+        assert!( !( -( tr('a')/tr('c') )).eq_roots_unordered( &( -( tr('a')/tr('d') ))));
+        assert!( ( -tr(1)-tr(1)-tr(2) ).eq_roots_unordered( &( -tr(1)-tr(2)-tr(1) )));
+        assert!( !( -tr(1)-tr(1)-tr(2) ).eq_roots_unordered( &( -tr(1)-tr(2)-tr(2) )));
+        assert!( !( -tr(1)-tr(2) ).eq_roots_unordered( &( -tr(1) )));
+        assert!( fr::<i32>().eq_roots_unordered( &fr() ));
+
+        let a = -( tr(1)/tr(2)/tr(3) )-tr(4);
+        let b = -tr(4)-( tr(1)/tr(3)/tr(2) );
+        assert!( !a.eq_roots_unordered( &b ));
+        assert!( a.eq_unordered( &b ));
+    }
+
+    #[test] fn map() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/( tr(2)/tr(3) )/tr(4) ) -tr(5) -( tr(6)/tr(7) );
+        let mapped = forest.map( |x| x.to_string() );
+        assert_eq!( mapped.to_string(), forest.to_string() );
+        assert_eq!( mapped.node_count(), 7 );
+        assert!( mapped.validate().is_ok() );
+
+        let mut visited = Vec::new();
+        assert_eq!( forest.map( |&x| { visited.push(x); x % 2 == 0 }),
+            -( tr(false)/( tr(true)/tr(false) )/tr(true) ) -tr(false) -( tr(true)/tr(false) ));
+        assert_eq!( visited, vec![ 1, 2, 3, 4, 5, 6, 7 ]);
+        assert_eq!( fr::<i32>().map( |x| x+1 ), fr() );
+    }
+
+    #[test] fn filter_trees() {
+        use crate::{fr, tr};
+
+        let forest = -tr(1) -( tr(2)/tr(5)/tr(6) ) -tr(3) -tr(4);
+        let evens = forest.filter_trees( |node| node.data() % 2 == 0 );
+        assert_eq!( evens, -( tr(2)/tr(5)/tr(6) ) -tr(4) );
+        assert_eq!( evens.node_count(), 4 );
+        assert!( evens.validate().is_ok() );
+        assert_eq!( forest.to_string(), "( 1 2( 5 6 ) 3 4 )" );
+        assert_eq!( forest.filter_trees( |node| node.has_no_child() ).to_string(), "( 1 3 4 )" );
+        assert_eq!( forest.filter_trees( |_| false ), fr() );
+    }
+
+    #[test] fn chunks_by() {
+        use crate::{fr, tr};
+
+        let data = |forest: &Forest<i32>, same: fn( &Node<i32>, &Node<i32> ) -> bool| forest.chunks_by( same )
+            .map( |run| run.iter().map( |node| *node.data() ).collect::<Vec<_>>() )
+            .collect::<Vec<_>>();
+        let forest = -tr(1)-tr(1)-tr(2)-tr(1);
+        assert_eq!( data( &forest, |a,b| a.data() == b.data() ), vec![ vec![1,1], vec![2], vec![1] ]);
+        assert_eq!( data( &forest, |a,b| a.data() <= b.data() ), vec![ vec![1,1,2], vec![1] ]);
+        assert_eq!( data( &forest, |_,_| true ), vec![ vec![1,1,2,1] ]);
+        assert_eq!( data( &forest, |_,_| false ), vec![ vec![1], vec![1], vec![2], vec![1] ]);
+        assert!( data( &fr(), |_,_| true ).is_empty() );
+
+        let forest = -( tr(0)/tr(1) ) -( tr(2)/tr(3) ) -tr(4);
+        assert_eq!( data( &forest, |a,b| a.degree() == b.degree() ), vec![ vec![0,2], vec![4] ]);
+    }
+
+    #[test] fn iter_mut_bfs() {
+        use crate::{fr, tr};
+
+        let mut forest = -( tr(1)/( tr(3)/tr(5) ) ) -( tr(2)/tr(4) );
+        let visited = forest.iter_mut_bfs().map( |(level, data)| { *data *= 10; (level, *data) }).collect::<Vec<_>>();
+        assert_eq!( visited, vec![ (0,10), (0,20), (1,30), (1,40), (2,50) ]);
+        assert_eq!( forest.to_string(), "( 10( 30( 50 ) ) 20( 40 ) )" );
+        assert_eq!( fr::<i32>().iter_mut_bfs().len(), 0 );
+    }
+
+    #[test] fn children_rev() {
+        use crate::{fr, tr};
+
+        let mut forest = -tr(1) -( tr(2)/tr(4) ) -tr(3);
+        assert_eq!( forest.children_rev().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 3, 2, 1 ]);
+        forest.push_back( tr(5) );
+        forest.pop_front();
+        assert_eq!( forest.children_rev().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 5, 3, 2 ]);
+        assert!( forest.children_rev().eq( forest.iter().collect::<Vec<_>>().into_iter().rev() ));
+        assert_eq!( fr::<i32>().children_rev().next(), None );
+    }
+
+    #[test] fn flat_map() {
+        use crate::{fr, tr};
+        use crate::rust::Cell;
+
+        let forest = -tr(1) -( tr(2)/tr(3) ) -tr(4);
+        let doubled = forest.flat_map( |tree| -tree.clone()-tree );
+        assert_eq!( doubled, -tr(1)-tr(1) -( tr(2)/tr(3) ) -( tr(2)/tr(3) ) -tr(4)-tr(4) );
+        assert_eq!( doubled.node_count(), 8 );
+        assert!( doubled.validate().is_ok() );
+
+        let children = doubled.flat_map( |mut tree| tree.abandon() );
+        assert_eq!( children.to_string(), "( 3 3 )" );
+        assert!( children.validate().is_ok() );
+
+        struct Counted<'a>( &'a Cell<usize> );
+        impl Drop for Counted<'_> { fn drop( &mut self ) { self.0.set( self.0.get() + 1 ); }}
+
+        let dropped = Cell::new( 0 );
+        let forest = -( tr( Counted( &dropped ))/tr( Counted( &dropped )) ) -tr( Counted( &dropped ));
+        let mut nth = 0;
+        let kept = forest.flat_map( |tree| { nth += 1; if nth == 1 { fr() } else { -tree }});
+        assert_eq!( dropped.get(), 2 );
+        assert_eq!( kept.degree(), 1 );
+        drop( kept );
+        assert_eq!( dropped.get(), 3 );
+    }
+
+    #[test] fn try_fold_trees() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/tr(2) ) -tr(3) -( tr(4)/tr(5)/tr(6) );
+        let sums = forest.try_fold_trees( Vec::new(), |mut sums, node| {
+            sums.push( node.iter_descendants().map( |node| *node.data() ).sum::<i32>() + node.data() );
+            Ok::<_,String>( sums )
+        });
+        assert_eq!( sums, Ok( vec![ 3, 3, 15 ]));
+
+        let mut visited = Vec::new();
+        let result = forest.try_fold_trees( 0, |count, node| {
+            visited.push( *node.data() );
+            if node.has_no_child() { Err( format!( "leaf {} after {} trees", node.data(), count ))} else { Ok( count+1 )}
+        });
+        assert_eq!( result, Err( "leaf 3 after 1 trees".to_owned() ));
+        assert_eq!( visited, vec![ 1, 3 ]);
+
+        assert_eq!( fr::<i32>().try_fold_trees( 7, |_, _| Err(()) ), Ok(7) );
+    }
+
+    #[test] fn truncate() {
+        use crate::tr;
+        use crate::rust::Cell;
+
+        struct Counted<'a>( &'a Cell<usize> );
+        impl Drop for Counted<'_> { fn drop( &mut self ) { self.0.set( self.0.get() + 1 ); }}
+
+        let dropped = Cell::new( 0 );
+        let mut forest = -tr( Counted( &dropped ))-tr( Counted( &dropped ))
+            -( tr( Counted( &dropped ))/tr( Counted( &dropped )) )-tr( Counted( &dropped ));
+        forest.truncate( 5 );
+        assert_eq!( forest.degree(), 4 );
+        forest.truncate( 2 );
+        assert_eq!( dropped.get(), 3 );
+        assert_eq!( forest.degree(), 2 );
+        assert_eq!( forest.node_count(), 2 );
+        forest.push_back( tr( Counted( &dropped )));
+        assert_eq!( forest.degree(), 3 );
+        forest.truncate( 0 );
+        assert_eq!( dropped.get(), 6 );
+        assert!( forest.has_no_child() );
+
+        let mut forest = -tr(1)-tr(2)-tr(3)-tr(4);
+        forest.truncate( 2 );
+        assert_eq!( forest.to_string(), "( 1 2 )" );
+    }
+
+    #[test] fn group_into_tree() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/tr(10) ) -tr(2) -tr(3) -tr(4) -tr(7);
+        let tree = forest.group_into_tree( 0, |node| if node.data() % 2 == 0 { -2 } else { -1 });
+        assert_eq!( tree, tr(0) /( tr(-1) /( tr(1)/tr(10) ) /tr(3) /tr(7) ) /( tr(-2)/tr(2)/tr(4) ));
+        assert_eq!( tree.node_count(), 9 );
+        assert!( tree.validate().is_ok() );
+
+        let tree = ( -tr(5)-tr(6) ).group_into_tree( 0, |_| 9 );
+        assert_eq!( tree, tr(0) /( tr(9)/tr(5)/tr(6) ));
+        assert_eq!( fr::<i32>().group_into_tree( 0, |_| 9 ), tr(0) );
+    }
+
+    #[test] fn zip_with() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/tr(2) ) -tr(3);
+        let other = -tr('a') -( tr('b')/tr('c') );
+        let zipped = forest.zip_with( other, |mut a, b| {
+            a.push_back( Tree::new( b.node_count() as i32 * 100 ));
+            a
+        }).unwrap();
+        assert_eq!( zipped, -( tr(1)/tr(2)/tr(100) ) -( tr(3)/tr(200) ));
+        assert!( zipped.validate().is_ok() );
+
+        assert_eq!( ( -tr(1)-tr(2) ).zip_with( -tr(1), |a, _:Tree<i32>| a ), None );
+        assert_eq!( fr::<i32>().zip_with( fr::<i32>(), |a, _| a ), Some( fr() ));
+    }
+
+    #[test] fn largest_and_smallest() {
+        use crate::{fr, tr};
+
+        let forest = -tr(1) -( tr(2)/tr(3)/tr(4) ) -( tr(5)/tr(6) );
+        assert_eq!( forest.largest().map( |node| *node.data() ), Some(2) );
+        assert_eq!( forest.smallest().map( |node| *node.data() ), Some(1) );
+
+        let forest = -( tr(1)/tr(2) ) -tr(3) -( tr(4)/tr(5) ) -tr(6);
+        assert_eq!( forest.largest().map( |node| *node.data() ), Some(1) );
+        assert_eq!( forest.smallest().map( |node| *node.data() ), Some(3) );
+
+        assert_eq!( fr::<i32>().largest(), None );
+        assert_eq!( fr::<i32>().smallest(), None );
+    }
+
+    #[test] fn interleave() {
+        use crate::{fr, tr};
+
+        let mut forest = -tr(1)-tr(3)-( tr(5)/tr(50) )-tr(7);
+        forest.interleave( -( tr(2)/tr(20) )-tr(4) );
+        assert_eq!( forest.to_string(), "( 1 2( 20 ) 3 4 5( 50 ) 7 )" );
+        assert_eq!( forest.node_count(), 8 );
+        assert!( forest.validate().is_ok() );
+
+        let mut forest = fr();
+        forest.interleave( -tr(1)-tr(2) );
+        assert_eq!( forest, -tr(1)-tr(2) );
+        forest.interleave( fr() );
+        assert_eq!( forest, -tr(1)-tr(2) );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] fn take_and_replace() {
+        use crate::{fr, tr};
+
+        let mut forest = -( tr(1)/tr(2) ) -tr(3);
+        let taken = forest.take();
+        assert_eq!( taken, -( tr(1)/tr(2) ) -tr(3) );
+        assert_eq!( forest, fr() );
+        assert!( taken.validate().is_ok() );
+        assert!( forest.validate().is_ok() );
+
+        forest.push_back( tr(4) );
+        let mut taken = forest.replace( taken );
+        assert_eq!( taken, -tr(4) );
+        assert_eq!( forest.node_count(), 3 );
+        taken.push_front( tr(5) );
+        forest.push_back( tr(6) );
+        assert_eq!( taken.to_string(), "( 5 4 )" );
+        assert_eq!( forest.to_string(), "( 1( 2 ) 3 6 )" );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] fn split_first() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/tr(2) ) -tr(3) -tr(4);
+        let (first, rest) = forest.split_first().unwrap();
+        assert_eq!( first, tr(1)/tr(2) );
+        assert_eq!( rest, -tr(3)-tr(4) );
+        assert_eq!( rest.node_count(), 2 );
+        assert!( rest.validate().is_ok() );
+
+        let (first, rest) = ( -tr(5) ).split_first().unwrap();
+        assert_eq!( first, tr(5) );
+        assert!( rest.has_no_child() );
+        assert_eq!( fr::<i32>().split_first(), None );
+    }
+
+    #[test] fn iter_len_after_mutation() {
+        use crate::tr;
+
+        let mut forest = -tr(1)-( tr(2)/tr(3) );
+        assert_eq!( forest.iter().len(), 2 );
+        forest.push_back( tr(4) );
+        assert_eq!( forest.iter().len(), 3 );
+        assert_eq!( forest.iter().count(), 3 );
+        assert_eq!( forest.iter_mut().len(), 3 );
+        forest.pop_front();
+        assert_eq!( forest.iter().len(), 2 );
+        assert_eq!( forest.iter().count(), 2 );
+        assert_eq!( forest.iter_mut().count(), 2 );
+
+        let mut iter = forest.iter();
+        iter.next();
+        assert_eq!( iter.len(), 1 );
+        iter.next();
+        assert_eq!( iter.len(), 0 );
+        assert!( iter.next().is_none() );
+
+        forest.pop_front();
+        forest.pop_front();
+        assert_eq!( forest.iter().len(), 0 );
+        assert_eq!( forest.iter().count(), 0 );
+    }
+
+    #[test] fn prepend_cases() {
+        use crate::{fr, tr};
+
+        let cases = vec![
+            ( fr(), fr(), "()" ),
+            ( fr(), -tr(1)-( tr(2)/tr(3) ), "( 1 2( 3 ) )" ),
+            ( -tr(1)-( tr(2)/tr(3) ), fr(), "( 1 2( 3 ) )" ),
+            ( -tr(1), -tr(2), "( 2 1 )" ),
+            ( -tr(1)-tr(2), -tr(3), "( 3 1 2 )" ),
+            ( -tr(1), -tr(2)-( tr(3)/tr(4) ), "( 2 3( 4 ) 1 )" ),
+            ( -tr(1)-tr(2), -tr(3)-tr(4), "( 3 4 1 2 )" ),
+        ];
+        for (mut forest, other, expected) in cases {
+            let degree = forest.degree() + other.degree();
+            let node_count = forest.node_count() + other.node_count();
+            forest.prepend( other );
+            assert_eq!( forest.to_string(), expected );
+            assert_eq!( forest.degree(), degree );
+            assert_eq!( forest.iter().len(), degree );
+            assert_eq!( forest.node_count(), node_count );
+            assert!( forest.validate().is_ok() );
+        }
+
+        let mut forest = -tr(2);
+        forest.prepend( -tr(1) );
+        forest.push_back( tr(3) );
+        forest.push_front( tr(0) );
+        assert_eq!( forest.to_string(), "( 0 1 2 3 )" );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] #[should_panic] fn swap_out_of_bounds() {
+        use crate::tr;
+
+        let mut forest = -tr(1)-tr(2);
+        forest.swap( 0, 2 );
+    }
+}
+
+#[cfg( miri )]
+mod miri_tests {
+    #[test] fn alternate_display() {
+        use crate::{fr, tr};
+
+        let forest = -( tr(1)/tr(2) ) -tr(3);
+        assert_eq!( format!( "{}", forest ), "( 1( 2 ) 3 )" );
+        assert_eq!( format!( "{:#}", forest ), "1\n  2\n3" );
+        assert_eq!( format!( "{:#}", fr::<i32>() ), "" );
+    }
+
+    #[test] fn has_no_child() {
+        use crate::{fr, tr};
+
+        let mut forest = fr();
+        assert!( forest.has_no_child() );
+        forest.push_back( tr(1) );
+        assert!( !forest.has_no_child() );
+    }
+
+    #[test] fn degree() {
+        use crate::Forest;
+
+        let forest = Forest::<i32>::from_tuple(( 0, (1,2), (3,4) ));
+        assert_eq!( forest.degree(), 3 );
+    }
+
+    #[test] fn node_count() {
         use crate::Forest;
 
         let forest = Forest::<i32>::from_tuple(( 0, (1,2), (3,4) ));
@@ -357,6 +1791,22 @@ mod miri_tests {
         assert_eq!( forest.to_string(), "( 10 20 )" );
     }
 
+    #[test] fn contains() {
+        use crate::tr;
+
+        let forest = -tr(1)-tr(2)/tr(4)-tr(3);
+        assert!( forest.contains( &2 ));
+        assert!( !forest.contains( &4 ));
+    }
+
+    #[test] fn position() {
+        use crate::tr;
+
+        let forest = -tr(1)-tr(2)/tr(4)-tr(3);
+        assert_eq!( forest.position( &3 ), Some(2) );
+        assert_eq!( forest.position( &4 ), None );
+    }
+
     #[test] fn push_front() {
         use crate::{Forest, Tree};
 
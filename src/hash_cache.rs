@@ -0,0 +1,175 @@
+//! `Tree` wrapper which caches its hash value, for trees hashed repeatedly.
+
+use crate::rust::*;
+
+use super::Tree;
+
+/// A `Tree` which remembers its hash value computed by the hasher builder `S`.
+///
+/// The hash value is computed lazily on first use and invalidated by any mutable access to the tree.
+/// It is exactly the value the builder's hasher produces for the plain `Tree`.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::{BuildHasher, BuildHasherDefault, Hash, Hasher};
+/// use trees::{HashCachingTree, tr};
+///
+/// let mut tree = HashCachingTree::with_hasher( tr(0) /tr(1)/tr(2), BuildHasherDefault::<DefaultHasher>::default() );
+///
+/// let mut hasher = tree.hasher().build_hasher();
+/// ( tr(0) /tr(1)/tr(2) ).hash( &mut hasher );
+/// assert_eq!( tree.hash_value(), hasher.finish() );
+///
+/// tree.push_back( tr(3) );
+/// assert_ne!( tree.hash_value(), hasher.finish() );
+/// ```
+pub struct HashCachingTree<T,S> {
+    tree   : Tree<T>,
+    cache  : Cell<Option<u64>>,
+    hasher : S,
+}
+
+#[cfg(not(feature="no_std"))]
+impl<T:Hash> HashCachingTree<T,BuildHasherDefault<DefaultHasher>> {
+    /// Wraps the tree, hashing it with `std`'s `DefaultHasher`.
+    pub fn new( tree: Tree<T> ) -> Self {
+        HashCachingTree::with_hasher( tree, BuildHasherDefault::default() )
+    }
+}
+
+impl<T:Hash,S:BuildHasher> HashCachingTree<T,S> {
+    /// Wraps the tree, hashing it with hashers built by `hasher`.
+    pub fn with_hasher( tree: Tree<T>, hasher: S ) -> Self {
+        HashCachingTree{ tree, cache: Cell::new( None ), hasher }
+    }
+
+    /// Returns the tree's hash value, computing it only if not cached.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::hash_map::RandomState;
+    /// use trees::{HashCachingTree, tr};
+    ///
+    /// let tree = HashCachingTree::with_hasher( tr(0) /tr(1), RandomState::new() );
+    /// assert_eq!( tree.hash_value(), tree.hash_value() );
+    /// ```
+    pub fn hash_value( &self ) -> u64 {
+        match self.cache.get() {
+            Some( hash ) => hash,
+            None => {
+                let hash = self.hasher.hash_one( &self.tree );
+                self.cache.set( Some( hash ));
+                hash
+            },
+        }
+    }
+
+    /// Returns the hasher builder.
+    pub fn hasher( &self ) -> &S { &self.hasher }
+
+    /// Unwraps the tree, discarding the cached hash value.
+    pub fn into_inner( self ) -> Tree<T> { self.tree }
+}
+
+impl<T,S> Deref for HashCachingTree<T,S> {
+    type Target = Tree<T>;
+
+    fn deref( &self ) -> &Tree<T> { &self.tree }
+}
+
+impl<T,S> DerefMut for HashCachingTree<T,S> {
+    fn deref_mut( &mut self ) -> &mut Tree<T> {
+        self.cache.set( None );
+        &mut self.tree
+    }
+}
+
+/// Feeds the cached hash value to the hasher.
+impl<T:Hash,S:BuildHasher> Hash for HashCachingTree<T,S> {
+    fn hash<H:Hasher>( &self, state: &mut H ) { state.write_u64( self.hash_value() )}
+}
+
+/// Compares the cached hash values first, then the trees.
+///
+/// Equal trees whose wrappers' hasher builders produce distinct hashers, e.g. distinct `RandomState`s,
+/// are not equal wrappers, as `Hash` requires.
+impl<T:Hash+PartialEq,S:BuildHasher> PartialEq for HashCachingTree<T,S> {
+    fn eq( &self, other: &Self ) -> bool { self.hash_value() == other.hash_value() && self.tree == other.tree }
+}
+
+impl<T:Hash+Eq,S:BuildHasher> Eq for HashCachingTree<T,S> {}
+
+impl<T:Debug,S> Debug for HashCachingTree<T,S> {
+    fn fmt( &self, f: &mut Formatter ) -> fmt::Result { self.tree.fmt( f )}
+}
+
+impl<T:Display,S> Display for HashCachingTree<T,S> {
+    fn fmt( &self, f: &mut Formatter ) -> fmt::Result { Display::fmt( &self.tree, f )}
+}
+
+#[cfg( all( test, not( feature = "no_std" )))]
+mod tests {
+    use super::*;
+    use crate::tr;
+
+    #[derive( PartialEq, Eq )]
+    struct Counted<'a>( i32, &'a Cell<usize> );
+
+    impl Hash for Counted<'_> {
+        fn hash<H:Hasher>( &self, state: &mut H ) {
+            self.1.set( self.1.get() + 1 );
+            self.0.hash( state );
+        }
+    }
+
+    fn plain_hash<T:Hash>( tree: &Tree<T> ) -> u64 {
+        let mut state = DefaultHasher::default();
+        tree.hash( &mut state );
+        state.finish()
+    }
+
+    #[test] fn second_hash_is_cached() {
+        let calls = Cell::new( 0 );
+        let mut tree = tr( Counted( 0, &calls ));
+        for i in 1..1000 {
+            tree.push_back( tr( Counted( i, &calls )));
+        }
+        let tree = HashCachingTree::new( tree );
+
+        let first = tree.hash_value();
+        assert_eq!( calls.get(), 1000 );
+        assert_eq!( tree.hash_value(), first );
+        assert_eq!( calls.get(), 1000 );
+        assert_eq!( first, plain_hash( &tree ));
+    }
+
+    #[test] fn mutation_invalidates_cache() {
+        let mut tree = HashCachingTree::new( tr(0) /tr(1)/tr(2) );
+        let before = tree.hash_value();
+
+        *tree.root_mut().front_mut().unwrap().data_mut() = 9;
+        let after = tree.hash_value();
+        assert_ne!( after, before );
+        assert_eq!( after, plain_hash( &( tr(0) /tr(9)/tr(2) )));
+        assert_eq!( tree, HashCachingTree::new( tr(0) /tr(9)/tr(2) ));
+    }
+
+    #[test] fn eq_agrees_with_hash() {
+        use std::collections::hash_map::RandomState;
+
+        let hasher = RandomState::new();
+        let a = HashCachingTree::with_hasher( tr(0)/tr(1), hasher.clone() );
+        let b = HashCachingTree::with_hasher( tr(0)/tr(1), hasher.clone() );
+        assert!( a == b );
+        assert_eq!( a.hash_value(), b.hash_value() );
+        assert!( a != HashCachingTree::with_hasher( tr(0)/tr(2), hasher ));
+
+        let c = HashCachingTree::with_hasher( tr(0)/tr(1), RandomState::new() );
+        assert_ne!( a.hash_value(), c.hash_value() );
+        assert!( a != c );
+        assert!( *a == *c );
+    }
+}
@@ -0,0 +1,113 @@
+//! Indented text import/export, one node per line.
+//!
+//! Each line holds the `Display` output of a node's data, preceded by the indent repeated as many times as the node's depth,
+//! e.g. `"1\n  2\n    3\n  4\n"` for `tr(1) /( tr(2)/tr(3) ) /tr(4)`.
+
+use crate::rust::*;
+
+use super::{Error, Tree};
+
+impl<T> Tree<T> {
+    /// Exports the tree as indented text, one node per line in pre-order, each line terminated with `'\n'`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.to_indented( "  " ), "1\n  2\n    3\n  4\n" );
+    /// ```
+    pub fn to_indented( &self, indent: &str ) -> String
+        where T: Display
+    {
+        use fmt::Write;
+
+        let mut text = String::new();
+        for (depth, node) in self.iter_depth() {
+            for _ in 0..depth {
+                text.push_str( indent );
+            }
+            writeln!( text, "{}", node.data() ).unwrap();
+        }
+        text
+    }
+}
+
+impl Tree<String> {
+    /// Parses a tree from indented text in the format produced by [`to_indented`](#method.to_indented).
+    ///
+    /// The indent is the leading whitespace of the first indented line,
+    /// and the leading whitespace of every line must be a repetition of it.
+    /// Blank lines are ignored. The data is the rest of the line after the indentation,
+    /// so it can not start with whitespace.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::from_indented( "a\n\tb\n\t\tc\n\td" ).unwrap();
+    /// assert_eq!( tree.to_string(), "a( b( c ) d )" );
+    ///
+    /// assert!( Tree::from_indented( "a\n    b\n  c" ).is_err() );
+    /// assert!( Tree::from_indented( "a\n  b\n      c" ).is_err() );
+    /// ```
+    pub fn from_indented( s: &str ) -> Result<Tree<String>, Error> {
+        let mut indent = None;
+        let mut pairs = Vec::new();
+
+        for (nth, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let data = line.trim_start();
+            let leading = &line[ ..line.len()-data.len() ];
+            let depth = if leading.is_empty() {
+                0
+            } else {
+                let indent = *indent.get_or_insert( leading );
+                if leading.len() % indent.len() != 0 || leading != indent.repeat( leading.len() / indent.len() ) {
+                    return Err( format!( "indented: inconsistent indentation at line {}", nth+1 ).into() );
+                }
+                leading.len() / indent.len()
+            };
+            match pairs.last() {
+                None if depth != 0 => return Err( format!( "indented: the root at line {} is indented", nth+1 ).into() ),
+                Some(_) if depth == 0 => return Err( format!( "indented: more than one root at line {}", nth+1 ).into() ),
+                Some( &(prev, _) ) if depth > prev+1 => return Err( format!( "indented: line {} is indented by more than one level", nth+1 ).into() ),
+                _ => pairs.push(( depth, data.to_owned() )),
+            }
+        }
+
+        Tree::from_depths( pairs ).ok_or_else( || "indented: no node found".into() )
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+    use crate::tr;
+
+    #[test] fn indented_round_trip() {
+        let tree = tr(0) /( tr(1)/( tr(2)/tr(3) )/tr(4) ) /tr(5) /( tr(6)/tr(7) );
+        for indent in &[ "  ", "\t", "   " ] {
+            let text = tree.to_indented( indent );
+            assert_eq!( Tree::from_indented( &text ).unwrap().to_indented( indent ), text );
+        }
+        let text = tree.to_indented( "    " );
+        assert_eq!( text, "0\n    1\n        2\n            3\n        4\n    5\n    6\n        7\n" );
+        assert_eq!( Tree::from_indented( &text ).unwrap().to_string(), tree.to_string() );
+        assert_eq!( Tree::from_indented( "0" ).unwrap(), tr( "0".to_owned() ));
+    }
+
+    #[test] fn indented_errors() {
+        assert_eq!( Tree::from_indented( "" ).unwrap_err().msg, "indented: no node found" );
+        assert_eq!( Tree::from_indented( "  a" ).unwrap_err().msg, "indented: the root at line 1 is indented" );
+        assert_eq!( Tree::from_indented( "a\n  b\nc" ).unwrap_err().msg, "indented: more than one root at line 3" );
+        assert_eq!( Tree::from_indented( "a\n  b\n      c" ).unwrap_err().msg, "indented: line 3 is indented by more than one level" );
+        assert_eq!( Tree::from_indented( "a\n  b\n   c" ).unwrap_err().msg, "indented: inconsistent indentation at line 3" );
+        assert_eq!( Tree::from_indented( "a\n  b\n\t\tc" ).unwrap_err().msg, "indented: inconsistent indentation at line 3" );
+        assert_eq!( Tree::from_indented( "\na\n\n  b\n" ).unwrap(), tr( "a".to_owned() ) /tr( "b".to_owned() ));
+    }
+}
@@ -5,6 +5,24 @@ use crate::rust::*;
 use super::{Forest, Iter, IterMut, Node, Tree};
 
 /// Forest's owning iterator.
+///
+/// Trees can be taken from both ends.
+///
+/// # Examples
+///
+/// ```
+/// use trees::tr;
+///
+/// let forest = -tr(1)-tr(2)-tr(3)-tr(4);
+/// let mut iter = forest.into_iter();
+/// assert_eq!( iter.next(), Some( tr(1) ));
+/// assert_eq!( iter.next_back(), Some( tr(4) ));
+/// assert_eq!( iter.len(), 2 );
+/// assert_eq!( iter.next_back(), Some( tr(3) ));
+/// assert_eq!( iter.next(), Some( tr(2) ));
+/// assert_eq!( iter.next(), None );
+/// assert_eq!( iter.next_back(), None );
+/// ```
 pub struct IntoIter<T> {
     pub(crate) forest : Forest<T>,
     pub(crate) marker : PhantomData<Tree<T>>,
@@ -23,6 +41,12 @@ impl<T> Iterator for IntoIter<T> {
     }
 }
 
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    fn next_back( &mut self ) -> Option<Tree<T>> {
+        self.forest.pop_back()
+    }
+}
+
 impl<T> ExactSizeIterator for IntoIter<T> {}
 
 impl<T> Drop for IntoIter<T> {
@@ -49,6 +73,47 @@ impl<T> IntoIterator for Forest<T> {
     fn into_iter( self ) -> IntoIter<T> { IntoIter{ forest: self, marker: PhantomData }}
 }
 
+/// Iterates over the forest's children, the same as `Forest::iter()`.
+///
+/// # Examples
+///
+/// ```
+/// use trees::tr;
+///
+/// let forest = -tr(1)-tr(2)-tr(3);
+/// let mut sum = 0;
+/// for child in &forest {
+///     sum += child.data();
+/// }
+/// assert_eq!( sum, 6 );
+/// ```
+impl<'a, T:'a> IntoIterator for &'a Forest<T> {
+    type Item = &'a Node<T>;
+    type IntoIter = Iter<'a,T>;
+
+    fn into_iter( self ) -> Self::IntoIter { self.iter() }
+}
+
+/// Iterates over the forest's children mutably, the same as `Forest::iter_mut()`.
+///
+/// # Examples
+///
+/// ```
+/// use trees::tr;
+///
+/// let mut forest = -tr(1)-tr(2)-tr(3);
+/// for mut child in &mut forest {
+///     *child.data_mut() *= 10;
+/// }
+/// assert_eq!( forest, -tr(10)-tr(20)-tr(30) );
+/// ```
+impl<'a, T:'a> IntoIterator for &'a mut Forest<T> {
+    type Item = Pin<&'a mut Node<T>>;
+    type IntoIter = IterMut<'a,T>;
+
+    fn into_iter( self ) -> Self::IntoIter { self.iter_mut() }
+}
+
 impl<'a, T:'a> IntoIterator for &'a Node<T> {
     type Item = Self;
     type IntoIter = Iter<'a,T>;
@@ -66,3 +131,20 @@ impl<'a, T:'a> IntoIterator for Pin<&'a mut Node<T>> {
         IterMut::once( Some( self.non_null() ))
     }
 }
+
+#[cfg( miri )]
+mod miri_tests {
+    #[test] fn next_back() {
+        use crate::tr;
+
+        let forest = -tr(1)-tr(2)-tr(3)-tr(4);
+        let mut iter = forest.into_iter();
+        assert_eq!( iter.next(), Some( tr(1) ));
+        assert_eq!( iter.next_back(), Some( tr(4) ));
+        assert_eq!( iter.len(), 2 );
+        assert_eq!( iter.next_back(), Some( tr(3) ));
+        assert_eq!( iter.next(), Some( tr(2) ));
+        assert_eq!( iter.next(), None );
+        assert_eq!( iter.next_back(), None );
+    }
+}
@@ -38,19 +38,36 @@ impl<T> Iterator for UncountedRawIter<T> {
 #[derive( Debug )]
 pub(crate) struct CountedRawIter<T> {
     iter : UncountedRawIter<T>,
+    back : Option<NonNull<Node<T>>>,
     len  : usize,
 }
 
 impl<T> CountedRawIter<T> {
-    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, len: usize ) -> CountedRawIter<T> {
+    // `back` is the last node to yield, which is the `len`-th node counting from `curr`.
+    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, back: Option<NonNull<Node<T>>>, len: usize ) -> CountedRawIter<T> {
         CountedRawIter {
             iter : UncountedRawIter::new( curr ),
+            back ,
             len  ,
         }
     }
 
     pub(crate) fn once( curr: Option<NonNull<Node<T>>> ) -> CountedRawIter<T> {
-        CountedRawIter::<T>::new( curr, 1 )
+        CountedRawIter::<T>::new( curr, curr, 1 )
+    }
+
+    pub(crate) fn peek( &self ) -> Option<NonNull<Node<T>>> {
+        if self.len == 0 { None } else { self.iter.curr }
+    }
+
+    pub(crate) fn split_at( self, mid: usize ) -> (CountedRawIter<T>, CountedRawIter<T>) {
+        assert!( mid <= self.len, "split_at index {} out of bounds for length {}", mid, self.len );
+        let mut rest = self.iter;
+        let mut back = None;
+        for _ in 0..mid {
+            back = rest.next();
+        }
+        (CountedRawIter{ iter: self.iter, back, len: mid }, CountedRawIter{ iter: rest, back: self.back, len: self.len - mid })
     }
 }
 
@@ -58,7 +75,7 @@ impl<T> Copy for CountedRawIter<T> {}
 
 impl<T> Clone for CountedRawIter<T> {
     fn clone( &self ) -> Self {
-        CountedRawIter{ iter: self.iter.clone(), len: self.len }
+        CountedRawIter{ iter: self.iter.clone(), back: self.back, len: self.len }
     }
 }
 
@@ -79,6 +96,19 @@ impl<T> Iterator for CountedRawIter<T> {
     }
 }
 
+impl<T> DoubleEndedIterator for CountedRawIter<T> {
+    fn next_back( &mut self ) -> Option<Self::Item> {
+        if self.len == 0 {
+            None
+        } else {
+            self.len -= 1;
+            let back = self.back;
+            self.back = back.and_then( |back| unsafe{ back.as_ref().prev });
+            back
+        }
+    }
+}
+
 /// An iterator over the child `Node`s of `Tree`, `Node` or `Forest`.
 ///
 /// This `struct` is created by [`Node::iter`] and [`Forest::iter`].
@@ -93,13 +123,33 @@ pub struct Iter<'a, T> {
 }
 
 impl<'a,T:'a> Iter<'a,T> {
-    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, len: usize ) -> Iter<'a,T> {
-        Iter{ iter: CountedRawIter::<T>::new( curr, len ), mark: PhantomData }
+    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, back: Option<NonNull<Node<T>>>, len: usize ) -> Iter<'a,T> {
+        Iter{ iter: CountedRawIter::<T>::new( curr, back, len ), mark: PhantomData }
     }
 
     pub(crate) fn once( curr: Option<NonNull<Node<T>>> ) -> Iter<'a,T> {
         Iter{ iter: CountedRawIter::<T>::once( curr ), mark: PhantomData }
     }
+
+    /// Returns the `Node` which `next()` would yield, without advancing the iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /tr(1)/tr(2);
+    /// let mut iter = tree.iter();
+    /// assert_eq!( iter.peek(), Some( tr(1).root() ));
+    /// assert_eq!( iter.peek(), iter.next() );
+    /// assert_eq!( iter.peek(), Some( tr(2).root() ));
+    /// iter.next();
+    /// assert_eq!( iter.peek(), None );
+    /// assert_eq!( iter.len(), 0 );
+    /// ```
+    pub fn peek( &self ) -> Option<&'a Node<T>> {
+        self.iter.peek().map( |node| unsafe{ &*node.as_ptr() })
+    }
 }
 
 impl<'a,T:'a> Iterator for Iter<'a,T> {
@@ -110,6 +160,15 @@ impl<'a,T:'a> Iterator for Iter<'a,T> {
     }
 
     fn size_hint( &self ) -> ( usize, Option<usize> ) { self.iter.size_hint() }
+
+    // Jumps to the last `Node` in O(1), without visiting the ones before it.
+    fn last( mut self ) -> Option<Self::Item> { self.next_back() }
+}
+
+impl<'a,T:'a> DoubleEndedIterator for Iter<'a,T> {
+    fn next_back( &mut self ) -> Option<Self::Item> {
+        self.iter.next_back().map( |node| unsafe{ &*node.as_ptr() })
+    }
 }
 
 impl<'a,T> ExactSizeIterator for Iter<'a, T> {}
@@ -129,13 +188,73 @@ pub struct IterMut<'a, T> {
 }
 
 impl<'a,T:'a> IterMut<'a,T> {
-    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, len: usize ) -> IterMut<'a,T> {
-        IterMut{ iter: CountedRawIter::<T>::new( curr, len ), mark: PhantomData }
+    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, back: Option<NonNull<Node<T>>>, len: usize ) -> IterMut<'a,T> {
+        IterMut{ iter: CountedRawIter::<T>::new( curr, back, len ), mark: PhantomData }
     }
 
     pub(crate) fn once( curr: Option<NonNull<Node<T>>> ) -> IterMut<'a,T> {
         IterMut{ iter: CountedRawIter::<T>::once( curr ),  mark: PhantomData }
     }
+
+    /// Returns the `Node` which `next()` would yield, without advancing the iterator.
+    /// The reference is borrowed from the iterator so that it cannot alias the one `next()` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1)/tr(2);
+    /// let mut iter = tree.iter_mut();
+    /// assert_eq!( iter.peek(), Some( tr(1).root() ));
+    /// *iter.next().unwrap().data_mut() = 3;
+    /// assert_eq!( iter.peek(), Some( tr(2).root() ));
+    /// iter.next();
+    /// assert_eq!( iter.peek(), None );
+    /// assert_eq!( tree.to_string(), "0( 3 2 )" );
+    /// ```
+    pub fn peek( &self ) -> Option<&Node<T>> {
+        self.iter.peek().map( |node| unsafe{ &*node.as_ptr() })
+    }
+
+    /// Divides the remaining children into two non-overlapping iterators, the first yielding `mid` children
+    /// and the second yielding the rest.
+    /// Like `IterMut` itself, the halves are not `Send`, because nodes may share reference counted storage
+    /// which structural mutation through `Pin<&mut Node<T>>` would touch.
+    /// To mutate the halves on separate threads, map their nodes to `&mut T` first,
+    /// which is `Send` if `T` is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1)/tr(2)/tr(3);
+    /// let (left, right) = tree.iter_mut().split_at( 1 );
+    /// assert_eq!( (left.len(), right.len()), (1, 2) );
+    /// left.for_each( |mut node| *node.data_mut() *= 10 );
+    /// right.for_each( |mut node| *node.data_mut() += 1 );
+    /// assert_eq!( tree.to_string(), "0( 10 3 4 )" );
+    ///
+    /// use std::{pin::Pin, thread};
+    ///
+    /// let (left, right) = tree.iter_mut().split_at( 2 );
+    /// let left = left.map( |node| Pin::into_inner( node ).data_mut() ).collect::<Vec<_>>();
+    /// let right = right.map( |node| Pin::into_inner( node ).data_mut() ).collect::<Vec<_>>();
+    /// thread::scope( |scope| {
+    ///     scope.spawn( || left.into_iter().for_each( |data| *data *= 2 ));
+    ///     scope.spawn( || right.into_iter().for_each( |data| *data *= 3 ));
+    /// });
+    /// assert_eq!( tree.to_string(), "0( 20 6 12 )" );
+    /// ```
+    pub fn split_at( self, mid: usize ) -> (IterMut<'a,T>, IterMut<'a,T>) {
+        let (left, right) = self.iter.split_at( mid );
+        (IterMut{ iter: left, mark: PhantomData }, IterMut{ iter: right, mark: PhantomData })
+    }
 }
 
 impl<'a,T:'a> Iterator for IterMut<'a,T> {
@@ -150,3 +269,285 @@ impl<'a,T:'a> Iterator for IterMut<'a,T> {
 
 impl<'a,T> ExactSizeIterator for IterMut<'a, T> {}
 impl<'a,T> FusedIterator for IterMut<'a, T> {}
+
+// Pre-order traversal of `len` nodes starting from `curr`, which is the first child of the subtree's root.
+// `depth` is the depth of `curr` relative to that root.
+#[derive( Debug )]
+pub(crate) struct RawDescendants<T> {
+    curr  : Option<NonNull<Node<T>>>,
+    len   : usize,
+    depth : usize,
+}
+
+impl<T> RawDescendants<T> {
+    pub(crate) fn new( node: &Node<T> ) -> RawDescendants<T> {
+        RawDescendants{ curr: node.head, len: node.node_count() - 1, depth: 1 }
+    }
+
+    pub(crate) fn depth( &self ) -> usize { self.depth }
+}
+
+impl<T> Iterator for RawDescendants<T> {
+    type Item = NonNull<Node<T>>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let item = self.curr?;
+        if self.len != 0 {
+            unsafe {
+                let mut node = item;
+                self.curr = node.as_ref().head;
+                if self.curr.is_some() {
+                    self.depth += 1;
+                } else {
+                    loop {
+                        self.curr = node.as_ref().next;
+                        if self.curr.is_some() {
+                            break;
+                        }
+                        node = node.as_ref().up.unwrap();
+                        self.depth -= 1;
+                    }
+                }
+            }
+        }
+        Some( item )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option<usize> ) {
+        (self.len, Some( self.len ))
+    }
+}
+
+/// An iterator over all the descendant `Node`s of a `Node` in pre-order, not including the node itself.
+///
+/// This `struct` is created by [`Node::iter_descendants`].
+/// See its document for more.
+///
+/// [`Node::iter_descendants`]: ../node/struct.Node.html#method.iter_descendants
+#[derive( Debug )]
+pub struct Descendants<'a, T> {
+    iter : RawDescendants<T>,
+    mark : PhantomData<&'a Node<T>>,
+}
+
+impl<'a,T:'a> Descendants<'a,T> {
+    pub(crate) fn new( node: &'a Node<T> ) -> Descendants<'a,T> {
+        Descendants{ iter: RawDescendants::new( node ), mark: PhantomData }
+    }
+}
+
+impl<'a,T:'a> Iterator for Descendants<'a,T> {
+    type Item = &'a Node<T>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        self.iter.next().map( |node| unsafe{ &*node.as_ptr() })
+    }
+
+    fn size_hint( &self ) -> ( usize, Option<usize> ) { self.iter.size_hint() }
+}
+
+impl<'a,T> ExactSizeIterator for Descendants<'a, T> {}
+impl<'a,T> FusedIterator for Descendants<'a, T> {}
+
+/// A mutable iterator over the data of all the descendant `Node`s of a `Node` in pre-order, not including the node itself.
+///
+/// Only the data is exposed, since restructuring a yielded node would invalidate the nodes yet to be visited.
+///
+/// This `struct` is created by [`Node::iter_descendants_mut`].
+/// See its document for more.
+///
+/// [`Node::iter_descendants_mut`]: ../node/struct.Node.html#method.iter_descendants_mut
+#[derive( Debug )]
+pub struct DescendantsMut<'a, T> {
+    iter : RawDescendants<T>,
+    mark : PhantomData<&'a mut Node<T>>,
+}
+
+impl<'a,T:'a> DescendantsMut<'a,T> {
+    pub(crate) fn new( node: &'a mut Node<T> ) -> DescendantsMut<'a,T> {
+        DescendantsMut{ iter: RawDescendants::new( node ), mark: PhantomData }
+    }
+}
+
+impl<'a,T:'a> Iterator for DescendantsMut<'a,T> {
+    type Item = &'a mut T;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        self.iter.next().map( |node| unsafe{ (*node.as_ptr()).data_mut() })
+    }
+
+    fn size_hint( &self ) -> ( usize, Option<usize> ) { self.iter.size_hint() }
+}
+
+impl<'a,T> ExactSizeIterator for DescendantsMut<'a, T> {}
+impl<'a,T> FusedIterator for DescendantsMut<'a, T> {}
+
+/// An iterator over a `Node` and all its descendants in pre-order, paired with their depths relative to the node.
+///
+/// This `struct` is created by [`Tree::iter_depth`].
+/// See its document for more.
+///
+/// [`Tree::iter_depth`]: ../tree/struct.Tree.html#method.iter_depth
+#[derive( Debug )]
+pub struct DepthIter<'a, T> {
+    node : Option<&'a Node<T>>,
+    iter : RawDescendants<T>,
+    mark : PhantomData<&'a Node<T>>,
+}
+
+impl<'a,T:'a> DepthIter<'a,T> {
+    pub(crate) fn new( node: &'a Node<T> ) -> DepthIter<'a,T> {
+        DepthIter{ node: Some( node ), iter: RawDescendants::new( node ), mark: PhantomData }
+    }
+}
+
+impl<'a,T:'a> Iterator for DepthIter<'a,T> {
+    type Item = (usize, &'a Node<T>);
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        match self.node.take() {
+            Some( node ) => Some(( 0, node )),
+            None => {
+                let depth = self.iter.depth();
+                self.iter.next().map( |node| (depth, unsafe{ &*node.as_ptr() }))
+            },
+        }
+    }
+
+    fn size_hint( &self ) -> ( usize, Option<usize> ) {
+        let len = self.iter.len + self.node.map_or( 0, |_| 1 );
+        (len, Some( len ))
+    }
+}
+
+impl<'a,T> ExactSizeIterator for DepthIter<'a, T> {}
+impl<'a,T> FusedIterator for DepthIter<'a, T> {}
+
+/// An iterator over a `Node` and all its descendants in post-order, i.e. children before their parents.
+///
+/// This `struct` is created by [`Tree::iter_postorder`].
+/// See its document for more.
+///
+/// [`Tree::iter_postorder`]: ../tree/struct.Tree.html#method.iter_postorder
+#[derive( Debug )]
+pub struct PostOrder<'a, T> {
+    curr : Option<NonNull<Node<T>>>,
+    len  : usize,
+    mark : PhantomData<&'a Node<T>>,
+}
+
+impl<'a,T:'a> PostOrder<'a,T> {
+    pub(crate) fn new( node: &'a Node<T> ) -> PostOrder<'a,T> {
+        PostOrder{ curr: Some( Self::leftmost_leaf( node.non_null() )), len: node.node_count(), mark: PhantomData }
+    }
+
+    pub(crate) fn leftmost_leaf( mut node: NonNull<Node<T>> ) -> NonNull<Node<T>> {
+        while let Some( head ) = unsafe{ node.as_ref().head } {
+            node = head;
+        }
+        node
+    }
+}
+
+impl<'a,T:'a> Iterator for PostOrder<'a,T> {
+    type Item = &'a Node<T>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let item = unsafe{ &*self.curr?.as_ptr() };
+        // The node itself is the last one, whose siblings and parent must not be visited.
+        self.curr = if self.len == 0 {
+            None
+        } else {
+            match item.next {
+                Some( next ) => Some( Self::leftmost_leaf( next )),
+                None => item.up,
+            }
+        };
+        Some( item )
+    }
+
+    fn size_hint( &self ) -> ( usize, Option<usize> ) { (self.len, Some( self.len ))}
+}
+
+impl<'a,T> ExactSizeIterator for PostOrder<'a, T> {}
+impl<'a,T> FusedIterator for PostOrder<'a, T> {}
+
+/// A mutable iterator over the data of `Node`s in level order, paired with their levels.
+///
+/// Only the data is exposed, since restructuring a yielded node would invalidate the nodes yet to be visited.
+///
+/// This `struct` is created by [`Tree::iter_mut_bfs`] and [`Forest::iter_mut_bfs`].
+/// See their documents for more.
+///
+/// [`Tree::iter_mut_bfs`]: ../tree/struct.Tree.html#method.iter_mut_bfs
+/// [`Forest::iter_mut_bfs`]: ../forest/struct.Forest.html#method.iter_mut_bfs
+#[derive( Debug )]
+pub struct BfsMut<'a, T> {
+    queue : VecDeque<(NonNull<Node<T>>, usize)>,
+    len   : usize,
+    mark  : PhantomData<&'a mut Node<T>>,
+}
+
+impl<'a,T:'a> BfsMut<'a,T> {
+    // Starts from `curr` and its next siblings at level 0, visiting `len` nodes in total.
+    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, len: usize ) -> BfsMut<'a,T> {
+        let mut queue = VecDeque::new();
+        queue.extend( UncountedRawIter::new( curr ).map( |node| (node, 0) ));
+        BfsMut{ queue, len, mark: PhantomData }
+    }
+}
+
+impl<'a,T:'a> Iterator for BfsMut<'a,T> {
+    type Item = (usize, &'a mut T);
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        let (node, level) = self.queue.pop_front()?;
+        self.len -= 1;
+        unsafe {
+            self.queue.extend( UncountedRawIter::new( node.as_ref().head ).map( |child| (child, level+1) ));
+            Some(( level, (*node.as_ptr()).data_mut() ))
+        }
+    }
+
+    fn size_hint( &self ) -> ( usize, Option<usize> ) { (self.len, Some( self.len ))}
+}
+
+impl<'a,T> ExactSizeIterator for BfsMut<'a, T> {}
+impl<'a,T> FusedIterator for BfsMut<'a, T> {}
+
+/// An iterator over the ancestor `Node`s of a `Node`, from its parent up to the root.
+///
+/// This `struct` is created by [`Node::iter_ancestors`].
+/// See its document for more.
+///
+/// [`Node::iter_ancestors`]: ../node/struct.Node.html#method.iter_ancestors
+#[derive( Debug )]
+pub struct Ancestors<'a, T> {
+    node : Option<&'a Node<T>>,
+}
+
+impl<'a,T:'a> Ancestors<'a,T> {
+    pub(crate) fn new( node: Option<&'a Node<T>> ) -> Ancestors<'a,T> {
+        Ancestors{ node }
+    }
+}
+
+impl<'a,T:'a> Iterator for Ancestors<'a,T> {
+    type Item = &'a Node<T>;
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        let node = self.node?;
+        self.node = node.parent();
+        Some( node )
+    }
+}
+
+impl<'a,T> FusedIterator for Ancestors<'a, T> {}
@@ -26,9 +26,9 @@ impl<T> Iterator for IterRc<T> {
 }
 
 impl<T> IterRc<T> {
-    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, len: usize ) -> Self {
+    pub(crate) fn new( curr: Option<NonNull<Node<T>>>, back: Option<NonNull<Node<T>>>, len: usize ) -> Self {
         IterRc {
-            iter: CountedRawIter::new( curr, len ),
+            iter: CountedRawIter::new( curr, back, len ),
             mark: PhantomData,
         }
     }
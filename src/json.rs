@@ -0,0 +1,357 @@
+//! JSON import/export with a fixed schema, without depending on serde.
+//!
+//! Each node is encoded as an object `{"data":...,"children":[...]}`,
+//! with the data being the `Display` output of the node's data as a JSON string.
+
+use crate::rust::*;
+
+use super::{Error, Node, Tree};
+
+impl<T> Tree<T> {
+    /// Exports the tree as JSON, each node encoded as `{"data":...,"children":[...]}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) );
+    /// assert_eq!( tree.to_json(),
+    ///     r#"{"data":"1","children":[{"data":"2","children":[{"data":"3","children":[]}]}]}"# );
+    /// ```
+    pub fn to_json( &self ) -> String
+        where T: Display
+    {
+        let mut json = String::new();
+        write_json( self.root(), &mut json );
+        json
+    }
+
+    /// Parses a tree from JSON in the schema produced by [`to_json`](#method.to_json).
+    ///
+    /// Whitespace between tokens is ignored. The "children" key is optional while the "data" key is mandatory.
+    /// Data can be a JSON string or a bare literal such as a number, which is then parsed with `FromStr`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) );
+    /// assert_eq!( Tree::from_json( &tree.to_json() ).unwrap(), tree );
+    ///
+    /// let json = r#" { "children" : [ { "data" : 2 } ], "data" : "1" } "#;
+    /// assert_eq!( Tree::<i32>::from_json( json ).unwrap(), tr(1)/tr(2) );
+    ///
+    /// let json = r#"{"data":1,"children":[{"children":[]}]}"#;
+    /// assert_eq!( Tree::<i32>::from_json( json ).unwrap_err().to_string(), r#"json: missing key "data""# );
+    /// ```
+    pub fn from_json( s: &str ) -> Result<Tree<T>, Error>
+        where T: FromStr
+    {
+        let mut parser = Parser{ chars: s.chars().peekable() };
+        let tree = parser.parse_node()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok( tree ),
+            Some( ch ) => Err( format!( "json: unexpected '{}' after the root object", ch ).into() ),
+        }
+    }
+}
+
+fn write_json<T:Display>( node: &Node<T>, json: &mut String ) {
+    write_open( node, json );
+    let mut iters = vec![ node.iter() ];
+    let mut first = true;
+    while let Some( iter ) = iters.last_mut() {
+        match iter.next() {
+            Some( child ) => {
+                if !first {
+                    json.push( ',' );
+                }
+                write_open( child, json );
+                iters.push( child.iter() );
+                first = true;
+            },
+            None => {
+                iters.pop();
+                json.push_str( "]}" );
+                first = false;
+            },
+        }
+    }
+}
+
+// Writes the node's data and opens its children array.
+fn write_open<T:Display>( node: &Node<T>, json: &mut String ) {
+    json.push_str( r#"{"data":"# );
+    write_string( &node.data().to_string(), json );
+    json.push_str( r#","children":["# );
+}
+
+fn write_string( s: &str, json: &mut String ) {
+    json.push( '"' );
+    for ch in s.chars() {
+        match ch {
+            '"'  => json.push_str( "\\\"" ),
+            '\\' => json.push_str( "\\\\" ),
+            '\n' => json.push_str( "\\n" ),
+            '\r' => json.push_str( "\\r" ),
+            '\t' => json.push_str( "\\t" ),
+            '\u{8}' => json.push_str( "\\b" ),
+            '\u{c}' => json.push_str( "\\f" ),
+            _ if ch < ' ' => json.push_str( &format!( "\\u{:04x}", ch as u32 )),
+            _ => json.push( ch ),
+        }
+    }
+    json.push( '"' );
+}
+
+// An object being parsed, whose closing brace has not been seen yet.
+struct Object<T> {
+    data         : Option<String>,
+    children     : Vec<Tree<T>>,
+    has_children : bool,
+}
+
+// What the parser expects next.
+enum State {
+    Open,
+    Key,
+    AfterMember,
+    Close,
+    AfterChild,
+}
+
+struct Parser<'a> {
+    chars : Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace( &mut self ) {
+        while let Some( ch ) = self.chars.peek() {
+            if ch.is_whitespace() {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect( &mut self, expected: char ) -> Result<(), Error> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some( ch ) if ch == expected => Ok(()),
+            Some( ch ) => Err( format!( "json: expected '{}', found '{}'", expected, ch ).into() ),
+            None => Err( format!( "json: expected '{}', found end of input", expected ).into() ),
+        }
+    }
+
+    // Parses with an explicit stack of the unfinished objects, so that deep trees do not overflow the call stack.
+    fn parse_node<T:FromStr>( &mut self ) -> Result<Tree<T>, Error> {
+        let mut stack = Vec::<Object<T>>::new();
+        let mut state = State::Open;
+        loop {
+            state = match state {
+                State::Open => {
+                    self.expect( '{' )?;
+                    stack.push( Object{ data: None, children: Vec::new(), has_children: false });
+                    self.skip_whitespace();
+                    if self.chars.peek() == Some( &'}' ) {
+                        self.chars.next();
+                        State::Close
+                    } else {
+                        State::Key
+                    }
+                },
+                State::Key => {
+                    self.skip_whitespace();
+                    let key = self.parse_string()?;
+                    self.expect( ':' )?;
+                    let object = stack.last_mut().unwrap();
+                    match key.as_str() {
+                        "data" if object.data.is_none() => {
+                            object.data = Some( self.parse_data()? );
+                            State::AfterMember
+                        },
+                        "children" if !object.has_children => {
+                            object.has_children = true;
+                            self.expect( '[' )?;
+                            self.skip_whitespace();
+                            if self.chars.peek() == Some( &']' ) {
+                                self.chars.next();
+                                State::AfterMember
+                            } else {
+                                State::Open
+                            }
+                        },
+                        "data" | "children" => return Err( format!( "json: duplicate key \"{}\"", key ).into() ),
+                        _ => return Err( format!( "json: unknown key \"{}\"", key ).into() ),
+                    }
+                },
+                State::AfterMember => {
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some( ',' ) => State::Key,
+                        Some( '}' ) => State::Close,
+                        Some( ch ) => return Err( format!( "json: expected ',' or '}}', found '{}'", ch ).into() ),
+                        None => return Err( "json: expected ',' or '}', found end of input".into() ),
+                    }
+                },
+                State::Close => {
+                    let object = stack.pop().unwrap();
+                    let data = object.data.ok_or( r#"json: missing key "data""# )?;
+                    let data = T::from_str( &data ).map_err( |_| format!( "json: invalid data \"{}\"", data ))?;
+                    let mut tree = Tree::new( data );
+                    object.children.into_iter().for_each( |child| tree.push_back( child ));
+                    match stack.last_mut() {
+                        Some( parent ) => parent.children.push( tree ),
+                        None => return Ok( tree ),
+                    }
+                    State::AfterChild
+                },
+                State::AfterChild => {
+                    self.skip_whitespace();
+                    match self.chars.next() {
+                        Some( ',' ) => State::Open,
+                        Some( ']' ) => State::AfterMember,
+                        Some( ch ) => return Err( format!( "json: expected ',' or ']', found '{}'", ch ).into() ),
+                        None => return Err( "json: expected ',' or ']', found end of input".into() ),
+                    }
+                },
+            };
+        }
+    }
+
+    // A JSON string, or the text of a bare literal such as a number.
+    fn parse_data( &mut self ) -> Result<String, Error> {
+        self.skip_whitespace();
+        if self.chars.peek() == Some( &'"' ) {
+            return self.parse_string();
+        }
+        let mut text = String::new();
+        while let Some( &ch ) = self.chars.peek() {
+            if ch.is_whitespace() || ch == ',' || ch == '}' || ch == ']' {
+                break;
+            }
+            text.push( ch );
+            self.chars.next();
+        }
+        if text.is_empty() {
+            Err( "json: expected data value".into() )
+        } else {
+            Ok( text )
+        }
+    }
+
+    fn parse_string( &mut self ) -> Result<String, Error> {
+        self.expect( '"' )?;
+        let mut s = String::new();
+        loop {
+            match self.chars.next() {
+                Some( '"' ) => return Ok( s ),
+                Some( '\\' ) => match self.chars.next() {
+                    Some( '"'  ) => s.push( '"' ),
+                    Some( '\\' ) => s.push( '\\' ),
+                    Some( '/'  ) => s.push( '/' ),
+                    Some( 'b'  ) => s.push( '\u{8}' ),
+                    Some( 'f'  ) => s.push( '\u{c}' ),
+                    Some( 'n'  ) => s.push( '\n' ),
+                    Some( 'r'  ) => s.push( '\r' ),
+                    Some( 't'  ) => s.push( '\t' ),
+                    Some( 'u'  ) => {
+                        let mut code = self.parse_hex4()?;
+                        if ( 0xD800..0xDC00 ).contains( &code ) {
+                            if self.chars.next() != Some( '\\' ) || self.chars.next() != Some( 'u' ) {
+                                return Err( "json: invalid unicode escape".into() );
+                            }
+                            let low = self.parse_hex4()?;
+                            if !( 0xDC00..0xE000 ).contains( &low ) {
+                                return Err( "json: invalid unicode escape".into() );
+                            }
+                            code = 0x10000 + (( code - 0xD800 ) << 10 ) + ( low - 0xDC00 );
+                        }
+                        s.push( char::from_u32( code ).ok_or( "json: invalid unicode escape" )? );
+                    },
+                    _ => return Err( "json: invalid escape sequence".into() ),
+                },
+                Some( ch ) => s.push( ch ),
+                None => return Err( "json: unterminated string".into() ),
+            }
+        }
+    }
+
+    fn parse_hex4( &mut self ) -> Result<u32, Error> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let digit = self.chars.next()
+                .and_then( |ch| ch.to_digit( 16 ))
+                .ok_or( "json: invalid unicode escape" )?;
+            code = code * 16 + digit;
+        }
+        Ok( code )
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test] fn json_escapes() {
+        let tree = Tree::new( "quote\" back\\slash\nnew line \u{1}".to_owned() ) / Tree::new( "é😀".to_owned() );
+        let json = tree.to_json();
+        assert_eq!( json, r#"{"data":"quote\" back\\slash\nnew line \u0001","children":[{"data":"é😀","children":[]}]}"# );
+        assert_eq!( Tree::<String>::from_json( &json ).unwrap(), tree );
+        assert_eq!( Tree::<String>::from_json( r#"{"data":"é😀"}"# ).unwrap(), Tree::new( "é😀".to_owned() ));
+    }
+
+    #[test] fn json_errors() {
+        let error = |json| Tree::<i32>::from_json( json ).unwrap_err().msg;
+        assert_eq!( error( r#"{"children":[]}"# ), r#"json: missing key "data""# );
+        assert_eq!( error( r#"{"data":1,"data":2}"# ), r#"json: duplicate key "data""# );
+        assert_eq!( error( r#"{"data":1,"child":[]}"# ), r#"json: unknown key "child""# );
+        assert_eq!( error( r#"{"data":"x"}"# ), r#"json: invalid data "x""# );
+        assert_eq!( error( r#"{"data":1,"children":[}"# ), "json: expected '{', found '}'" );
+        assert_eq!( error( r#"{"data":1} {"# ), "json: unexpected '{' after the root object" );
+        assert_eq!( error( r#"{"data":1"# ), "json: expected ',' or '}', found end of input" );
+        assert_eq!( error( r#"{"data":"\uD800\u0041"}"# ), "json: invalid unicode escape" );
+        assert_eq!( error( r#"{"data":"\uD800 \uDC00"}"# ), "json: invalid unicode escape" );
+        assert_eq!( error( r#"{"data":"\uDC00"}"# ), "json: invalid unicode escape" );
+    }
+
+    #[test] fn deep_json() {
+        let depth = 200_000;
+        let mut chain = Tree::new( 0 );
+        for data in 1..depth {
+            chain = Tree::new( data ) / chain;
+        }
+        let json = chain.to_json();
+        let tree = Tree::<i32>::from_json( &json ).unwrap();
+        assert_eq!( tree.node_count(), depth as usize );
+        assert_eq!( tree, chain );
+    }
+}
+
+#[cfg( miri )]
+mod miri_tests {
+    #[test] fn to_json() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(2)/tr(3) );
+        assert_eq!( tree.to_json(),
+            r#"{"data":"1","children":[{"data":"2","children":[{"data":"3","children":[]}]}]}"# );
+    }
+
+    #[test] fn from_json() {
+        use crate::{Tree, tr};
+
+        let tree = tr(1) /( tr(2)/tr(3) );
+        assert_eq!( Tree::from_json( &tree.to_json() ).unwrap(), tree );
+
+        let json = r#" { "children" : [ { "data" : 2 } ], "data" : "1" } "#;
+        assert_eq!( Tree::<i32>::from_json( json ).unwrap(), tr(1)/tr(2) );
+
+        let json = r#"{"data":1,"children":[{"children":[]}]}"#;
+        assert_eq!( Tree::<i32>::from_json( json ).unwrap_err().to_string(), r#"json: missing key "data""# );
+    }
+}
@@ -78,25 +78,46 @@
 //! 7. Support exclusive ownership with static borrow check.
 //!
 //! 8. Support shared ownership with dynamic borrow check.
+//!
+//! # no_std
+//!
+//! Enabling the "no_std" feature builds the crate without `std`, on top of `core` and `alloc`.
+//! All `Tree`/`Forest`/`Node` APIs remain available, including `Debug` and `Display` formatting.
+//! The `events` and `binary` modules are left out since they rely on `std::io`,
+//! and so is `edges` which takes `std::collections::HashMap`s.
+//!
+//! # check_alias
+//!
+//! Enabling the "check_alias" feature makes debug builds verify, on every insertion of a `Tree` as a child or sibling,
+//! that its root is not already owned by the destination, panicking instead of freeing the node twice later.
+//! It is meant for catching misuse of unsafe code that forges `Tree`s, and costs a walk of the destination per insertion.
 
 #![cfg_attr( feature = "no_std", no_std )]
 
+#[cfg( all( test, feature = "no_std" ))]
+#[macro_use]
+extern crate std;
+
 #[doc( hidden )]
 pub mod rust {
     #[cfg(not(feature="no_std"))] pub use std::borrow::{Borrow, ToOwned};
     #[cfg(not(feature="no_std"))] pub use std::boxed::Box;
     #[cfg(not(feature="no_std"))] pub use std::cell::{Cell, Ref, RefMut, RefCell};
-    #[cfg(not(feature="no_std"))] pub use std::collections::VecDeque;
+    #[cfg(not(feature="no_std"))] pub use std::collections::{BTreeMap, BTreeSet, VecDeque};
     #[cfg(not(feature="no_std"))] pub use std::cmp::Ordering::{self, *};
+    #[cfg(not(feature="no_std"))] pub use std::convert::{Infallible, TryFrom};
     #[cfg(not(feature="no_std"))] pub use std::fmt::{self, Debug, Display, Formatter};
-    #[cfg(not(feature="no_std"))] pub use std::hash::{Hasher, Hash};
-    #[cfg(not(feature="no_std"))] pub use std::iter::{Iterator, FromIterator, IntoIterator, FusedIterator};
+    #[cfg(not(feature="no_std"))] pub use std::collections::hash_map::DefaultHasher;
+    #[cfg(not(feature="no_std"))] pub use std::hash::{BuildHasher, BuildHasherDefault, Hasher, Hash};
+    #[cfg(not(feature="no_std"))] pub use std::iter::{self, Iterator, FromIterator, IntoIterator, FusedIterator, Peekable};
     #[cfg(not(feature="no_std"))] pub use std::marker::{PhantomData, Unpin};
     #[cfg(not(feature="no_std"))] pub use std::mem::{self, forget, transmute, MaybeUninit};
     #[cfg(not(feature="no_std"))] pub use std::ops::{Add, AddAssign, Deref, DerefMut, Div, Neg, Sub, SubAssign};
     #[cfg(not(feature="no_std"))] pub use std::pin::Pin;
     #[cfg(not(feature="no_std"))] pub use std::ptr::{self, NonNull, null, null_mut};
     #[cfg(not(feature="no_std"))] pub use std::rc::{Rc, Weak};
+    #[cfg(not(feature="no_std"))] pub use std::str::{Chars, FromStr};
+    #[cfg(not(feature="no_std"))] pub use std::string::{String, ToString};
     #[cfg(not(feature="no_std"))] pub use std::vec::Vec;
 
     #[cfg(feature="no_std")] extern crate core;
@@ -104,25 +125,24 @@ pub mod rust {
     #[cfg(feature="no_std")] pub use self::alloc::borrow::{Borrow, ToOwned};
     #[cfg(feature="no_std")] pub use self::alloc::boxed::Box;
     #[cfg(feature="no_std")] pub use self::alloc::string::String;
-    #[cfg(feature="no_std")]
-                #[cfg(test)] pub use self::alloc::string::ToString;
-    #[cfg(feature="no_std")] pub use self::alloc::collections::VecDeque;
-    #[cfg(feature="no_std")]
-                #[cfg(test)] pub use self::alloc::format;
+    #[cfg(feature="no_std")] pub use self::alloc::string::ToString;
+    #[cfg(feature="no_std")] pub use self::alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+    #[cfg(feature="no_std")] pub use self::alloc::format;
     #[cfg(feature="no_std")] pub use self::alloc::rc::{Rc, Weak};
-    #[cfg(feature="no_std")]
-                #[cfg(test)] pub use self::alloc::vec;
+    #[cfg(feature="no_std")] pub use self::alloc::vec;
     #[cfg(feature="no_std")] pub use self::alloc::vec::Vec;
     #[cfg(feature="no_std")] pub use core::cell::{Cell, Ref, RefMut, RefCell};
     #[cfg(feature="no_std")] pub use core::cmp::Ordering::{self, *};
+    #[cfg(feature="no_std")] pub use core::convert::{Infallible, TryFrom};
     #[cfg(feature="no_std")] pub use core::fmt::{self, Debug, Display, Formatter};
-    #[cfg(feature="no_std")] pub use core::hash::{Hasher, Hash};
-    #[cfg(feature="no_std")] pub use core::iter::{Iterator, FromIterator, IntoIterator, FusedIterator};
+    #[cfg(feature="no_std")] pub use core::hash::{BuildHasher, BuildHasherDefault, Hasher, Hash};
+    #[cfg(feature="no_std")] pub use core::iter::{self, Iterator, FromIterator, IntoIterator, FusedIterator, Peekable};
     #[cfg(feature="no_std")] pub use core::marker::{PhantomData, Unpin};
     #[cfg(feature="no_std")] pub use core::mem::{self, forget, transmute, MaybeUninit};
     #[cfg(feature="no_std")] pub use core::ops::{Add, AddAssign, Deref, DerefMut, Div, Neg, Sub, SubAssign};
     #[cfg(feature="no_std")] pub use core::pin::Pin;
     #[cfg(feature="no_std")] pub use core::ptr::{self, NonNull, null, null_mut};
+    #[cfg(feature="no_std")] pub use core::str::{Chars, FromStr};
 }
 
 #[macro_use]
@@ -143,27 +163,59 @@ pub mod forest;
 pub use forest::Forest;
 
 pub mod node;
-pub use node::Node;
+pub use node::{Node, NodeId};
 pub(crate) use node::Data;
 
 pub(crate) mod node_vec;
 pub(crate) use node_vec::NodeVec;
 
 pub mod iter;
-pub use iter::{Iter, IterMut};
+pub use iter::{Ancestors, BfsMut, DepthIter, Descendants, DescendantsMut, Iter, IterMut, PostOrder};
 pub(crate) use iter::CountedRawIter;
 
 pub mod into_iter;
 pub use into_iter::IntoIter;
 
+pub mod cursor;
+pub use cursor::Cursor;
+
+pub mod entry;
+pub use entry::ChildEntry;
+
+pub mod hash_cache;
+pub use hash_cache::HashCachingTree;
+
 pub mod heap;
 
 pub mod walk;
-pub use walk::{TreeWalk, ForestWalk};
+pub use walk::{TreeWalk, ForestWalk, WalkIndexed, WalkPos};
 
 pub mod notation;
 pub use notation::{tr, fr};
 
+pub mod newick;
+
+pub mod json;
+
+pub mod xml;
+
+pub mod indented;
+
+pub mod folded;
+
+#[cfg( not( feature = "no_std" ))]
+pub mod events;
+#[cfg( not( feature = "no_std" ))]
+pub use events::{TreeEvent, parse_events};
+
+#[cfg( not( feature = "no_std" ))]
+pub mod binary;
+#[cfg( not( feature = "no_std" ))]
+pub use binary::{ToBytes, FromBytes};
+
+#[cfg( not( feature = "no_std" ))]
+pub mod edges;
+
 pub mod iter_rc;
 pub use iter_rc::IterRc;
 
@@ -172,6 +224,9 @@ pub use rc::{RcNode, WeakNode};
 
 pub(crate) mod bfs_impls;
 
+#[cfg( feature = "rayon" )]
+pub mod par;
+
 
 #[derive(Debug)]
 pub struct Error {
@@ -190,15 +245,16 @@ impl From<&str> for Error {
     }
 }
 
-use std::fmt;
+use crate::rust::*;
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.msg)
     }
 }
 
-use std::error;
-impl error::Error for Error {
+#[cfg(not(feature="no_std"))]
+impl std::error::Error for Error {
     fn description(&self) -> &str {
         &self.msg
     }
@@ -206,7 +262,6 @@ impl error::Error for Error {
 
 
 
-use std::pin::Pin;
 impl<T> Node<T> {
     pub fn locate_first_by_path<'s, 't>(&'s self, mut path: impl Iterator<Item=&'t T> + Clone ) -> Option<&'s Node<T>>
         where T: 't + PartialEq
@@ -316,10 +371,6 @@ impl<T> Node<T> {
 
 
 
-use std::convert::{TryFrom};
-use crate::rust::Formatter;
-
-
 impl TryFrom<&str> for Tree<String> {
     type Error = Error;
     fn try_from(item: &str) -> Result<Self, Self::Error> {
@@ -327,6 +378,17 @@ impl TryFrom<&str> for Tree<String> {
     }
 }
 
+// Token of the text format of trees, see `impl TryFrom<String> for Tree<String>`.
+enum Token {
+    Open,
+    Close,
+    Data(String),
+}
+
+/// Parses the text format written by `Display`, or by `to_string_escaped()` if some data are quoted.
+///
+/// A token starting with `"` is read up to the next unescaped `"`, with `\"` and `\\` standing for `"` and `\`,
+/// so that it may contain whitespace, `(` and `)`.
 impl TryFrom<String> for Tree<String> {
     type Error = Error;
 
@@ -336,41 +398,57 @@ impl TryFrom<String> for Tree<String> {
         let mut legal = 0;
 
         let mut t = String::from("");
-        tree_string.chars().for_each(|v| {
+        let mut chars = tree_string.chars();
+        while let Some(v) = chars.next() {
             match v {
                 '(' | ')' | ' ' => {
                     if !t.is_empty() {
-                        tokens.push(t.clone());
+                        tokens.push(Token::Data(t.clone()));
                         t = "".to_string();
                     }
-                    if v !=' ' {
-                        tokens.push(v.to_string());
-                    }
-                    legal = if v == '(' {
-                        legal + 1
+                    if v == '(' {
+                        tokens.push(Token::Open);
+                        legal += 1;
                     } else if v == ')' {
-                        legal - 1
-                    } else {
-                        legal
+                        tokens.push(Token::Close);
+                        legal -= 1;
                     }
                 },
+                '"' if t.is_empty() => {
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some('\\') => match chars.next() {
+                                Some(c) => t.push(c),
+                                None => return Err("unterminated quoted data".into()),
+                            },
+                            Some(c) => t.push(c),
+                            None => return Err("unterminated quoted data".into()),
+                        }
+                    }
+                    tokens.push(Token::Data(t.clone()));
+                    t = "".to_string();
+                },
                 _ => t.push(v)
             }
-        });
+        }
 
-        if !t.is_empty() { tokens.push(t) }
+        if !t.is_empty() { tokens.push(Token::Data(t)) }
 
         // the number of '(' is not equal to the number of ')'
         if legal !=0 || tokens.len() == 0 {
             return Err("() is not closed or no root".into())
         }
 
-        let mut tree = Tree::new(String::from(&tokens[0]));
+        let mut tree = match &tokens[0] {
+            Token::Data(data) => Tree::new(data.clone()),
+            _ => return Err("() is not closed or no root".into()),
+        };
         let mut forests: Vec<Forest<String>> = Vec::new();
-        tokens.iter().skip(1).for_each(|v| {
-            match v.as_str() {
-                "(" => forests.push(Forest::new()),
-                ")" => {
+        tokens.into_iter().skip(1).for_each(|v| {
+            match v {
+                Token::Open => forests.push(Forest::new()),
+                Token::Close => {
                     let last_forest = forests.pop().unwrap();
                     if let Some(father_forest) = forests.last_mut() {
                         last_forest.into_iter().for_each(|v| {
@@ -381,7 +459,7 @@ impl TryFrom<String> for Tree<String> {
                         tree.root_mut().prepend(last_forest)
                     }
                 },
-                _ => forests.last_mut().unwrap().push_back(Tree::new(String::from(v)))
+                Token::Data(data) => forests.last_mut().unwrap().push_back(Tree::new(data))
             }
         });
         Ok(tree)
@@ -405,6 +483,17 @@ mod extend_tests {
         assert_eq!(Tree::try_from("a").unwrap(), Tree::new("a".to_string()));
     }
 
+    #[test] fn test_try_from_quoted_string() {
+        let tree = tr("hello (world)".to_string()) /(tr("a b".to_string())/tr(String::new())) /tr(r#""\x"#.to_string()) /tr("c".to_string());
+        let escaped = tree.to_string_escaped();
+        assert_eq!(escaped, r#""hello (world)"( "a b"( "" ) "\"\\x" c )"#);
+        assert_eq!(Tree::try_from(escaped).unwrap(), tree);
+
+        assert_eq!(Tree::try_from("0( \"1 2\"(3) )").unwrap(), tr("0".to_string()) /(tr("1 2".to_string())/tr("3".to_string())));
+        assert!(Tree::try_from(r#"0( "1 )"#).is_err());
+        assert!(Tree::try_from(r#"0( "1\"#).is_err());
+    }
+
 
     #[test] fn test_node_locate_by_path() {
         let mut tree = tr(0) /(tr(1)/tr(2)) /(tr(3)/tr(4));
@@ -7,7 +7,7 @@ macro_rules! impl_debug_display_for_collection {
         }
 
         impl<T:Display> Display for $ty<T> {
-            fn fmt( &self, f: &mut Formatter ) -> fmt::Result { write!( f, "{}", self.$($agent)+ )}
+            fn fmt( &self, f: &mut Formatter ) -> fmt::Result { Display::fmt( self.$($agent)+, f )}
         }
     };
 }
@@ -33,7 +33,15 @@ macro_rules! impl_debug_display_for_forest {
 
         impl<T:Display> Display for $ty<T> {
             fn fmt( &self, f: &mut Formatter ) -> fmt::Result {
-                if self.has_no_child() {
+                if f.alternate() {
+                    for (nth, child) in self.$($agent)+.enumerate() {
+                        if nth != 0 {
+                            writeln!( f )?;
+                        }
+                        Display::fmt( &child, f )?;
+                    }
+                    Ok(())
+                } else if self.has_no_child() {
                     write!( f, "()" )
                 } else {
                     write!( f, "( " )?;
@@ -113,7 +121,27 @@ macro_rules! impl_debug_display_for_node {
 
         impl<T:Display> Display for $ty<T> {
             fn fmt( &self, f: &mut Formatter ) -> fmt::Result {
-                if self.has_no_child() {
+                if f.alternate() {
+                    // one node per line, indented by `width` fill characters per level.
+                    let indent = f.width().unwrap_or( 2 );
+                    let fill = f.fill();
+                    write!( f, "{}", self.$($data)+ )?;
+                    let mut iters = vec![ self.$iter() ];
+                    while let Some( iter ) = iters.last_mut() {
+                        match iter.next() {
+                            Some( child ) => {
+                                writeln!( f )?;
+                                for _ in 0..iters.len()*indent {
+                                    write!( f, "{}", fill )?;
+                                }
+                                write!( f, "{}", child.$($data)+ )?;
+                                iters.push( child.$iter() );
+                            },
+                            None => { iters.pop(); },
+                        }
+                    }
+                    Ok(())
+                } else if self.has_no_child() {
                     write!( f, "{}", self.$($data)+ )
                 } else {
                     write!( f, "{}", self.$($data)+ )?;
@@ -131,8 +159,26 @@ macro_rules! impl_debug_display_for_node {
 macro_rules! impl_order_relations_for_node {
     ( $ty:ident, $iter:ident, $($data:tt)+ ) => {
         impl<T:PartialEq> PartialEq for $ty<T> {
-            fn eq( &self, other: &Self ) -> bool { self.$($data)+ == other.$($data)+ && self.$iter().eq( other.$iter() )}
-            fn ne( &self, other: &Self ) -> bool { self.$($data)+ != other.$($data)+ || self.$iter().ne( other.$iter() )}
+            // Walks both trees side by side without recursion, so that deep trees do not overflow the stack.
+            fn eq( &self, other: &Self ) -> bool {
+                if self.$($data)+ != other.$($data)+ {
+                    return false;
+                }
+                let mut iters = vec![ (self.$iter(), other.$iter()) ];
+                while let Some( (iter, other_iter) ) = iters.last_mut() {
+                    match (iter.next(), other_iter.next()) {
+                        (Some( child ), Some( other_child )) => {
+                            if child.$($data)+ != other_child.$($data)+ {
+                                return false;
+                            }
+                            iters.push(( child.$iter(), other_child.$iter() ));
+                        },
+                        (None, None) => { iters.pop(); },
+                        _ => return false,
+                    }
+                }
+                true
+            }
         }
 
         impl<T:Eq> Eq for $ty<T> {}
@@ -0,0 +1,252 @@
+//! Newick format, the nested parentheses notation used for phylogenetic trees.
+//!
+//! Children are listed in parentheses before their parent's label, separated by commas,
+//! and the whole tree is terminated with a semicolon, e.g. `(2,3)1;`.
+
+use crate::rust::*;
+
+use super::{Error, Forest, Node, Tree};
+
+// Characters that can not appear in an unquoted label.
+const SPECIAL_CHARS: &str = " \t\r\n()[]':;,";
+
+impl<T> Tree<T> {
+    /// Exports the tree in Newick format.
+    ///
+    /// Labels containing spaces, commas, parentheses or other Newick punctuation
+    /// are single-quoted, with embedded single quotes doubled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// assert_eq!( tr(1).to_newick(), "1;" );
+    /// assert_eq!( ( tr(1)/tr(2)/tr(3) ).to_newick(), "(2,3)1;" );
+    /// assert_eq!( ( tr(1) /( tr(2)/tr(3) ) /tr(4) ).to_newick(), "((3)2,4)1;" );
+    /// assert_eq!( ( tr("a b") /tr("it's") ).to_newick(), "('it''s')'a b';" );
+    /// ```
+    pub fn to_newick( &self ) -> String
+        where T: Display
+    {
+        let mut newick = String::new();
+        write_newick( self.root(), &mut newick );
+        newick.push( ';' );
+        newick
+    }
+}
+
+impl Tree<String> {
+    /// Parses a tree in Newick format, which must be terminated with a semicolon.
+    ///
+    /// Labels are optional and can be single-quoted, in which case two consecutive single quotes
+    /// stand for one. Missing labels become empty strings. Branch lengths are not supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::from_newick( "(A,B)C;" ).unwrap();
+    /// assert_eq!( tree.to_string(), "C( A B )" );
+    ///
+    /// let tree = Tree::from_newick( "((A,B)C,D)E;" ).unwrap();
+    /// assert_eq!( tree.to_string(), "E( C( A B ) D )" );
+    /// assert_eq!( tree.to_newick(), "((A,B)C,D)E;" );
+    ///
+    /// let tree = Tree::from_newick( " ( 'a b' , ) ; " ).unwrap();
+    /// assert_eq!( tree, tr( String::new() ) /tr( "a b".to_owned() ) /tr( String::new() ));
+    ///
+    /// assert!( Tree::from_newick( "((A,B)C;" ).is_err() );
+    /// assert!( Tree::from_newick( "(A,B))C;" ).is_err() );
+    /// assert!( Tree::from_newick( "(A,B)C" ).is_err() );
+    /// ```
+    pub fn from_newick( s: &str ) -> Result<Tree<String>, Error> {
+        let mut chars = s.chars().peekable();
+        let mut forests = Vec::<Forest<String>>::new(); // children of unclosed parentheses.
+        let mut children = None;                        // children closed by ')', waiting for the label.
+        let mut label = None;
+
+        let make_tree = |label: &mut Option<String>, children: &mut Option<Forest<String>>| {
+            let mut tree = Tree::new( label.take().unwrap_or_default() );
+            if let Some( forest ) = children.take() {
+                tree.append( forest );
+            }
+            tree
+        };
+
+        while let Some( ch ) = chars.next() {
+            match ch {
+                '(' => {
+                    if label.is_some() || children.is_some() {
+                        return Err( "newick: unexpected '('".into() );
+                    }
+                    forests.push( Forest::new() );
+                },
+                ',' | ')' => {
+                    let tree = make_tree( &mut label, &mut children );
+                    match forests.last_mut() {
+                        Some( forest ) => forest.push_back( tree ),
+                        None => return Err( format!( "newick: unbalanced parentheses, unexpected '{}'", ch ).into() ),
+                    }
+                    if ch == ')' {
+                        children = forests.pop();
+                    }
+                },
+                ';' => {
+                    if !forests.is_empty() {
+                        return Err( "newick: unbalanced parentheses, missing ')'".into() );
+                    }
+                    if chars.any( |ch| !ch.is_whitespace() ) {
+                        return Err( "newick: unexpected characters after ';'".into() );
+                    }
+                    return Ok( make_tree( &mut label, &mut children ));
+                },
+                ':' => return Err( "newick: branch lengths are not supported".into() ),
+                '[' | ']' => return Err( "newick: comments are not supported".into() ),
+                _ if ch.is_whitespace() => (),
+                _ => {
+                    if label.is_some() {
+                        return Err( "newick: unexpected label".into() );
+                    }
+                    let mut text = String::new();
+                    if ch == '\'' {
+                        loop {
+                            match chars.next() {
+                                Some( '\'' ) => if chars.peek() == Some( &'\'' ) {
+                                    chars.next();
+                                    text.push( '\'' );
+                                } else {
+                                    break;
+                                },
+                                Some( ch ) => text.push( ch ),
+                                None => return Err( "newick: unterminated quoted label".into() ),
+                            }
+                        }
+                    } else {
+                        text.push( ch );
+                        while let Some( &ch ) = chars.peek() {
+                            if SPECIAL_CHARS.contains( ch ) {
+                                break;
+                            }
+                            text.push( ch );
+                            chars.next();
+                        }
+                    }
+                    label = Some( text );
+                },
+            }
+        }
+
+        if forests.is_empty() {
+            Err( "newick: missing ';'".into() )
+        } else {
+            Err( "newick: unbalanced parentheses, missing ')'".into() )
+        }
+    }
+}
+
+fn write_newick<T:Display>( node: &Node<T>, newick: &mut String ) {
+    if node.has_no_child() {
+        write_label( &node.data().to_string(), newick );
+        return;
+    }
+    newick.push( '(' );
+    let mut stack = vec![ (node, node.iter()) ];
+    let mut first = true;
+    while let Some( (parent, iter) ) = stack.last_mut() {
+        match iter.next() {
+            Some( child ) => {
+                if !first {
+                    newick.push( ',' );
+                }
+                if child.has_no_child() {
+                    write_label( &child.data().to_string(), newick );
+                    first = false;
+                } else {
+                    newick.push( '(' );
+                    stack.push( (child, child.iter()) );
+                    first = true;
+                }
+            },
+            None => {
+                newick.push( ')' );
+                write_label( &parent.data().to_string(), newick );
+                stack.pop();
+                first = false;
+            },
+        }
+    }
+}
+
+fn write_label( label: &str, newick: &mut String ) {
+    if label.contains( |ch| SPECIAL_CHARS.contains( ch )) {
+        newick.push( '\'' );
+        newick.push_str( &label.replace( '\'', "''" ));
+        newick.push( '\'' );
+    } else {
+        newick.push_str( label );
+    }
+}
+
+#[cfg( miri )]
+mod miri_tests {
+    #[test] fn to_newick() {
+        use crate::tr;
+
+        assert_eq!( tr(1).to_newick(), "1;" );
+        assert_eq!( ( tr(1)/tr(2)/tr(3) ).to_newick(), "(2,3)1;" );
+        assert_eq!( ( tr(1) /( tr(2)/tr(3) ) /tr(4) ).to_newick(), "((3)2,4)1;" );
+        assert_eq!( ( tr("a b") /tr("it's") ).to_newick(), "('it''s')'a b';" );
+    }
+
+    #[test] fn from_newick() {
+        use crate::{Tree, tr};
+
+        let tree = Tree::from_newick( "(A,B)C;" ).unwrap();
+        assert_eq!( tree.to_string(), "C( A B )" );
+
+        let tree = Tree::from_newick( "((A,B)C,D)E;" ).unwrap();
+        assert_eq!( tree.to_string(), "E( C( A B ) D )" );
+        assert_eq!( tree.to_newick(), "((A,B)C,D)E;" );
+
+        let tree = Tree::from_newick( " ( 'a b' , ) ; " ).unwrap();
+        assert_eq!( tree, tr( String::new() ) /tr( "a b".to_owned() ) /tr( String::new() ));
+
+        assert!( Tree::from_newick( "((A,B)C;" ).is_err() );
+        assert!( Tree::from_newick( "(A,B))C;" ).is_err() );
+        assert!( Tree::from_newick( "(A,B)C" ).is_err() );
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test] fn newick_round_trip() {
+        let newick = "(('it''s',B)C,(D)'E F',G)H;";
+        assert_eq!( Tree::from_newick( newick ).unwrap().to_newick(), newick );
+    }
+
+    #[test] fn deep_newick() {
+        let depth = 200_000;
+        let mut chain = Tree::new( 0.to_string() );
+        for data in 1..depth {
+            chain = Tree::new( data.to_string() ) / chain;
+        }
+        let newick = chain.to_newick();
+        assert!( newick.starts_with( "((((" ));
+        assert!( newick.ends_with( ")199998)199999;" ));
+        assert_eq!( Tree::from_newick( &newick ).unwrap(), chain );
+    }
+
+    #[test] fn newick_errors() {
+        assert_eq!( Tree::from_newick( "(A,B)C" ).unwrap_err().msg, "newick: missing ';'" );
+        assert_eq!( Tree::from_newick( "((A,B)C" ).unwrap_err().msg, "newick: unbalanced parentheses, missing ')'" );
+        assert_eq!( Tree::from_newick( "A,B;" ).unwrap_err().msg, "newick: unbalanced parentheses, unexpected ','" );
+        assert_eq!( Tree::from_newick( "('A,B)C;" ).unwrap_err().msg, "newick: unterminated quoted label" );
+        assert_eq!( Tree::from_newick( "A B;" ).unwrap_err().msg, "newick: unexpected label" );
+        assert_eq!( Tree::from_newick( "A(B);" ).unwrap_err().msg, "newick: unexpected '('" );
+        assert_eq!( Tree::from_newick( "A;B" ).unwrap_err().msg, "newick: unexpected characters after ';'" );
+    }
+}
@@ -6,7 +6,7 @@ use crate::Size;
 
 use crate::rust::*;
 
-use super::{Forest, Iter, IterMut, NodeVec, Tree};
+use super::{Ancestors, ChildEntry, Descendants, DescendantsMut, Error, Forest, Iter, IterMut, NodeVec, Tree};
 
 /// Data associated with `Node`.
 #[derive( Debug, PartialEq, Eq, PartialOrd, Ord, Hash )]
@@ -70,6 +70,7 @@ impl<T> Data<T> {
 }
 
 impl<T> AsRef<T> for Data<T> {
+    #[inline]
     fn as_ref( &self ) -> &T {
         match self {
             Data::Piled    { ref data, .. } => data,
@@ -80,6 +81,7 @@ impl<T> AsRef<T> for Data<T> {
 }
 
 impl<T> AsMut<T> for Data<T> {
+    #[inline]
     fn as_mut( &mut self ) -> &mut T {
         match self {
             Data::Piled    { ref mut data, .. } => data,
@@ -101,19 +103,73 @@ pub struct Node<T> {
     pub(crate) data : Data<T>,
 }
 
+/// Opaque identity of a `Node`, usable as a key of side tables.
+///
+/// It is derived from the node's address, so it stays the same as long as the node is alive,
+/// even if the node is moved to another place in the tree or to another tree.
+/// Once the node is dropped, its `NodeId` may be reused by a new node.
+#[derive( Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash )]
+pub struct NodeId( usize );
+
 impl<T> Default for Node<T> {
     fn default() -> Self {
         Node{ prev: None, next: None, head: None, tail: None, up: None, size: Size::default(), data: Data::default() }
     }
 }
 
+// Panics if the tree to insert is aliased by a node already owned by the destination, which would end up freed twice.
+// The tree is leaked rather than dropped when panicking.
+#[cfg( all( debug_assertions, feature = "check_alias" ))]
+macro_rules! check_alias {
+    ($node:expr, $tree:ident) => {
+        if $node.is_aliased_by( &$tree ) {
+            forget( $tree );
+            panic!( "the tree to insert is already owned by the destination" );
+        }
+    };
+}
+
 impl<T> Node<T> {
     /// Reference of its associated data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// assert_eq!( tr(5).root().data(), &5 );
+    /// ```
+    #[inline]
     pub fn data( &self ) -> &T { self.data.as_ref() }
 
-    /// Mutable reeference of its associated data.
+    /// Mutable reference of its associated data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(5);
+    /// *tree.root_mut().data_mut() += 1;
+    /// assert_eq!( tree.root().data(), &6 );
+    /// ```
+    #[inline]
     pub fn data_mut( &mut self ) -> &mut T { self.data.as_mut() }
 
+    /// Returns the identity of the node, which is valid until the node is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /tr(1)/tr(2);
+    /// let first = tree.front().unwrap();
+    /// assert_eq!( first.id(), tree.iter().next().unwrap().id() );
+    /// assert_ne!( first.id(), tree.back().unwrap().id() );
+    /// ```
+    pub fn id( &self ) -> NodeId { NodeId( self as *const Node<T> as usize )}
+
     /// Returns `true` if `Node` has no child nodes.
     ///
     /// # Examples
@@ -128,6 +184,21 @@ impl<T> Node<T> {
     /// ```
     pub fn has_no_child( &self ) -> bool { self.head.is_none() }
 
+    /// Returns `true` if `Node` is a leaf, i.e. it has no child nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /tr(2)/tr(3);
+    /// assert!( !tree.root().is_leaf() );
+    /// assert_eq!( tree.root().degree(), 2 );
+    /// assert!( tr(5).root().is_leaf() );
+    /// assert_eq!( tr(5).root().degree(), 0 );
+    /// ```
+    pub fn is_leaf( &self ) -> bool { self.has_no_child() }
+
     /// Returns the number of child nodes in `Node`.
     ///
     /// # Examples
@@ -189,6 +260,126 @@ impl<T> Node<T> {
         None
     }
 
+    /// Provides an iterator over the ancestor nodes of this node,
+    /// starting from its parent and ending at the root node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) );
+    /// let deepest = tree.front().unwrap().front().unwrap();
+    /// let ancestors = deepest.iter_ancestors().map( |node| *node.data() ).collect::<Vec<_>>();
+    /// assert_eq!( ancestors, vec![ 2, 1 ]);
+    /// assert_eq!( tree.root().iter_ancestors().next(), None );
+    /// ```
+    pub fn iter_ancestors( &self ) -> Ancestors<'_,T> { Ancestors::new( self.parent() )}
+
+    /// Provides an iterator over the siblings after this node, in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /tr(1)/tr(2)/tr(3);
+    /// let first = tree.front().unwrap();
+    /// assert_eq!( first.following_siblings().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 2, 3 ]);
+    /// assert_eq!( tree.back().unwrap().following_siblings().next(), None );
+    /// assert_eq!( tree.root().following_siblings().next(), None );
+    /// ```
+    pub fn following_siblings( &self ) -> Iter<'_,T> {
+        let mut len = 0;
+        let mut back = None;
+        let mut next = self.next;
+        while let Some( node ) = next {
+            len += 1;
+            back = next;
+            next = unsafe{ node.as_ref().next };
+        }
+        Iter::new( self.next, back, len )
+    }
+
+    /// Provides an iterator over all the descendant nodes of this node in pre-order,
+    /// not including the node itself.
+    ///
+    /// The traversal follows the nodes' parent and sibling links,
+    /// so it neither allocates nor modifies the tree.
+    /// Chain it after the node itself for a full pre-order traversal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// use std::iter::once;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// let descendants = tree.root().iter_descendants().map( |node| *node.data() ).collect::<Vec<_>>();
+    /// assert_eq!( descendants, vec![ 2, 3, 4 ]);
+    /// assert_eq!( tree.root().iter_descendants().len(), 3 );
+    /// assert_eq!( tree.back().unwrap().iter_descendants().next(), None );
+    ///
+    /// let preorder = once( tree.root() ).chain( tree.root().iter_descendants() );
+    /// assert_eq!( preorder.map( |node| *node.data() ).collect::<Vec<_>>(), tree.to_vec() );
+    /// ```
+    pub fn iter_descendants( &self ) -> Descendants<'_,T> { Descendants::new( self )}
+
+    /// Provides an iterator over the mutable data of all the descendant nodes of this node in pre-order,
+    /// not including the node itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// tree.root_mut().iter_descendants_mut().for_each( |data| *data *= 10 );
+    /// assert_eq!( tree.to_string(), "1( 20( 30 ) 40 )" );
+    /// ```
+    pub fn iter_descendants_mut( &mut self ) -> DescendantsMut<'_,T> { DescendantsMut::new( self )}
+
+    /// Calls `f` on this node and then on all its descendants in pre-order, iteratively.
+    ///
+    /// The children of a node are collected after `f` has been called on it, so `f` may add or remove children,
+    /// and the children present after the call are visited. If `f` detaches the node, its subtree is not visited.
+    /// Siblings inserted by `f` are not visited either.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// tree.root_mut().update_preorder( |mut node| *node.data_mut() += 1 );
+    /// assert_eq!( tree.to_string(), "2( 3( 4 ) 5 )" );
+    /// ```
+    pub fn update_preorder<F>( &mut self, mut f: F )
+        where F: FnMut( Pin<&mut Node<T>> )
+    {
+        let mut stack = vec![ self.non_null() ];
+        while let Some( node ) = stack.pop() {
+            let (up, prev, next) = unsafe{ (node.as_ref().up, node.as_ref().prev, node.as_ref().next) };
+            f( unsafe{ Pin::new_unchecked( &mut *node.as_ptr() )});
+
+            // The node may have been detached and freed by `f`, so look for it between its original siblings,
+            // which `f` can not reach, before touching it.
+            if let Some( up ) = up {
+                let mut curr = match prev {
+                    Some( prev ) => unsafe{ prev.as_ref().next },
+                    None => unsafe{ up.as_ref().head },
+                };
+                while curr != next && curr != Some( node ) {
+                    curr = curr.and_then( |curr| unsafe{ curr.as_ref().next });
+                }
+                if curr != Some( node ) {
+                    continue;
+                }
+            }
+            stack.extend( unsafe{ node.as_ref() }.iter().rev().map( Node::non_null ));
+        }
+    }
+
     /// Inserts sib tree before `self`.
     /// The newly inserted node will not be iterated over by the currently running iterator.
     ///
@@ -202,6 +393,8 @@ impl<T> Node<T> {
     /// assert_eq!( tree.to_string(), "0( 3 1 3 2 )" );
     /// ```
     pub fn insert_prev_sib( &mut self, mut sib: Tree<T> ) {
+        #[cfg( all( debug_assertions, feature = "check_alias" ))]
+        check_alias!( self, sib );
         let mut up = self.up.unwrap();
 
         sib.root_mut_().up = Some( up );
@@ -232,6 +425,8 @@ impl<T> Node<T> {
     /// assert_eq!( tree.to_string(), "0( 1 3 2 3 )" );
     /// ```
     pub fn insert_next_sib( &mut self, mut sib: Tree<T> ) {
+        #[cfg( all( debug_assertions, feature = "check_alias" ))]
+        check_alias!( self, sib );
         let mut up = self.up.unwrap();
 
         sib.root_mut_().up = Some( up );
@@ -252,6 +447,10 @@ impl<T> Node<T> {
 
     /// The subtree departs from its parent and becomes an indepent `Tree`.
     ///
+    /// # Panics
+    ///
+    /// Panics if the node is a tree's root, which has no parent to depart from.
+    ///
     /// # Examples
     /// ```
     /// use trees::{tr, fr};
@@ -259,10 +458,14 @@ impl<T> Node<T> {
     /// let mut forest = fr()-tr(1)-tr(2)-tr(3);
     /// forest.iter_mut().for_each( |mut sub| { sub.detach(); });
     /// assert_eq!( forest, fr() );
+    ///
+    /// let mut tree = tr(0) /tr(1)/tr(2)/tr(3);
+    /// assert_eq!( tree.root_mut().child_mut(1).unwrap().detach(), tr(2) );
+    /// assert_eq!( tree.to_string(), "0( 1 3 )" );
     /// ```
     pub fn detach( &mut self ) -> Tree<T> {
         unsafe {
-            let mut up = self.up.unwrap();
+            let mut up = self.up.expect( "the root node can not be detached" );
 
             match up.as_ref().size.degree {
                 1 => {
@@ -293,6 +496,110 @@ impl<T> Node<T> {
         Tree{ root: self.non_null(), mark: PhantomData }
     }
 
+    /// Removes the `index`-th child, putting its own children in its place in order, and returns its data,
+    /// or returns `None` if there is no such child. For a detached `Tree`, use `Tree::into_forest()` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr('0') /tr('1') /( tr('2')/tr('a')/tr('b') ) /tr('3');
+    /// assert_eq!( tree.root_mut().dissolve_child(1), Some('2') );
+    /// assert_eq!( tree.to_string(), "0( 1 a b 3 )" );
+    /// assert_eq!( tree.root_mut().dissolve_child(4), None );
+    /// ```
+    pub fn dissolve_child( &mut self, index: usize ) -> Option<T> {
+        let mut child = self.locate( &[ index ])?;
+        let (prev, next) = unsafe{ (child.as_ref().prev, child.as_ref().next) };
+        let mut tree = unsafe{ child.as_mut().detach() };
+        let mut forest = tree.abandon();
+        match (prev, next) {
+            (None, _) => self.prepend( forest ),
+            (Some(_), None) => self.append( forest ),
+            (Some( mut prev ), Some( mut next )) => if !forest.has_no_child() {
+                forest.set_up( self );
+                unsafe {
+                    prev.as_mut().connect_next( forest.root_mut_().head.unwrap().as_mut() );
+                    forest.root_mut_().tail.unwrap().as_mut().connect_next( next.as_mut() );
+                }
+                let size = forest.root_().size;
+                self.inc_sizes( size.degree, size.descendants );
+                forest.clear();
+            },
+        }
+        Some( tree.into_data() )
+    }
+
+    // Returns `true` if the root of the tree is already in the tree or forest containing `self`.
+    #[cfg( all( debug_assertions, feature = "check_alias" ))]
+    fn is_aliased_by( &self, tree: &Tree<T> ) -> bool {
+        let mut top = self;
+        while let Some( up ) = top.up {
+            top = unsafe{ &*up.as_ptr() };
+        }
+        let owns = |node: &Node<T>| node.non_null() == tree.root || node.iter_descendants().any( |node| node.non_null() == tree.root );
+        if top.is_forest() { top.iter().any( owns ) } else { owns( top ) }
+    }
+
+    /// Splits the children list into two at the given index,
+    /// returning the children from `at` on as a `Forest` and keeping the ones before `at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > degree()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1)/tr(2)/tr(3);
+    /// let forest = tree.root_mut().split_off( 1 );
+    /// assert_eq!( tree.to_string(), "0( 1 )" );
+    /// assert_eq!( forest.to_string(), "( 2 3 )" );
+    /// ```
+    pub fn split_off( &mut self, at: usize ) -> Forest<T> {
+        let degree = self.degree();
+        assert!( at <= degree, "split_off index {} out of bounds for degree {}", at, degree );
+        let mut forest = Forest::new();
+        if at == degree {
+            return forest;
+        }
+
+        let up = forest.root_().non_null();
+        let mut node_cnt = 0;
+        unsafe {
+            let tail = self.tail;
+            let mut head = self.locate( &[ at ]).unwrap();
+            match head.as_ref().prev {
+                Some( mut prev ) => {
+                    prev.as_mut().next = None;
+                    self.tail = Some( prev );
+                },
+                None => {
+                    self.head = None;
+                    self.tail = None;
+                },
+            }
+            head.as_mut().prev = None;
+
+            let mut curr = Some( head );
+            while let Some( mut node ) = curr {
+                node.as_mut().up = Some( up );
+                node_cnt += node.as_ref().node_count();
+                curr = node.as_ref().next;
+            }
+
+            let root = forest.root_mut_();
+            root.head = Some( head );
+            root.tail = tail;
+        }
+        self.dec_sizes( degree - at, node_cnt );
+        forest.root_mut_().inc_sizes( degree - at, node_cnt );
+        forest
+    }
+
     /// Provides a forward iterator over child `Node`s
     ///
     /// # Examples
@@ -312,11 +619,43 @@ impl<T> Node<T> {
     /// ```
     pub fn iter<'a, 's:'a>( &'s self ) -> Iter<'a,T> {
         match self.head {
-            Some( child ) => Iter::new( Some( child ), self.degree() ),
-            None => Iter::new( None, 0 ),
+            Some( child ) => Iter::new( Some( child ), self.tail, self.degree() ),
+            None => Iter::new( None, None, 0 ),
         }
     }
 
+    /// Provides an iterator over all overlapping windows of `size` consecutive children, like `slice::windows()`.
+    /// Each window is collected into a `Vec`, since the children are not stored contiguously.
+    /// Yields nothing if there are fewer than `size` children.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /tr(1)/tr(2)/tr(3)/tr(4);
+    /// let windows = tree.windows( 2 )
+    ///     .map( |window| window.iter().map( |node| *node.data() ).collect::<Vec<_>>() )
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!( windows, vec![ vec![ 1, 2 ], vec![ 2, 3 ], vec![ 3, 4 ]]);
+    /// assert_eq!( tree.windows( 5 ).next(), None );
+    /// ```
+    pub fn windows( &self, size: usize ) -> impl Iterator<Item=Vec<&Node<T>>> + '_ {
+        assert!( size != 0, "window size must be non-zero" );
+        let mut iter = self.iter();
+        let mut window = iter.by_ref().take( size-1 ).collect::<Vec<_>>();
+        iter::from_fn( move || {
+            window.push( iter.next()? );
+            let next = window.clone();
+            window.remove( 0 );
+            Some( next )
+        })
+    }
+
     /// Provides a forward iterator over child `Node`s with mutable references.
     ///
     /// # Examples
@@ -332,8 +671,8 @@ impl<T> Node<T> {
     /// ```
     pub fn iter_mut<'a, 's:'a>( &'s mut self ) -> IterMut<'a,T> {
         match self.head {
-            Some( child ) => IterMut::new( Some( child ), self.degree() ),
-            None => IterMut::new( None, 0 ),
+            Some( child ) => IterMut::new( Some( child ), self.tail, self.degree() ),
+            None => IterMut::new( None, None, 0 ),
         }
     }
 
@@ -361,6 +700,57 @@ impl<T> Node<T> {
         self.tail.map( |tail| unsafe{ Pin::new_unchecked( &mut *tail.as_ptr() )})
     }
 
+    /// Returns the child at `index`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /tr(1)/tr(2)/tr(3);
+    /// assert_eq!( tree.child(2), Some( tr(3).root() ));
+    /// assert_eq!( tree.child(3), None );
+    /// ```
+    pub fn child( &self, index: usize ) -> Option<&Node<T>> {
+        self.iter().nth( index )
+    }
+
+    /// Returns a mutable pointer to the child at `index`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1)/tr(2)/tr(3);
+    /// *tree.root_mut().child_mut(1).unwrap().data_mut() = 5;
+    /// assert_eq!( tree.to_string(), "0( 1 5 3 )" );
+    /// assert!( tree.root_mut().child_mut(3).is_none() );
+    /// ```
+    pub fn child_mut( &mut self, index: usize ) -> Option<Pin<&mut Node<T>>> {
+        self.iter_mut().nth( index )
+    }
+
+    /// Looks up the first child whose data equals `key`, for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1)/tr(2);
+    /// assert!( tree.root_mut().child_entry( &2 ).is_occupied() );
+    /// tree.root_mut().child_entry( &3 ).or_insert();
+    /// tree.root_mut().child_entry( &1 ).or_insert().push_back( tr(4) );
+    /// assert_eq!( tree.to_string(), "0( 1( 4 ) 2 3 )" );
+    /// ```
+    pub fn child_entry<'a,'k>( self: Pin<&'a mut Self>, key: &'k T ) -> ChildEntry<'a,'k,T>
+        where T: PartialEq
+    {
+        let child = self.iter().find( |child| child.data() == key ).map( |child| child.non_null() );
+        ChildEntry::new( self, key, child )
+    }
+
     /// Adds the tree as the first child.
     ///
     /// # Examples
@@ -375,6 +765,8 @@ impl<T> Node<T> {
     /// assert_eq!( tree.to_string(), "0( 2 1 )" );
     /// ```
     pub fn push_front( &mut self, mut tree: Tree<T> ) {
+        #[cfg( all( debug_assertions, feature = "check_alias" ))]
+        check_alias!( self, tree );
         tree.root_mut_().set_up( self );
         if self.has_no_child() {
             self.set_tail( tree.root() );
@@ -400,6 +792,8 @@ impl<T> Node<T> {
     /// assert_eq!( tree.to_string(), "0( 1 2 )" );
     /// ```
     pub fn push_back( &mut self, mut tree: Tree<T> ) {
+        #[cfg( all( debug_assertions, feature = "check_alias" ))]
+        check_alias!( self, tree );
         tree.root_mut_().set_up( self );
         if self.has_no_child() {
             self.set_head( tree.root() );
@@ -543,6 +937,276 @@ impl<T> Node<T> {
         }
     }
 
+    /// Replaces all the children with the forest's trees, returning the old children as a `Forest`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(1) /tr(2)/tr(3);
+    /// let old = tree.root_mut().set_children( -tr(8)-tr(9) );
+    /// assert_eq!( tree.to_string(), "1( 8 9 )" );
+    /// assert_eq!( old.to_string(), "( 2 3 )" );
+    /// ```
+    pub fn set_children( &mut self, forest: Forest<T> ) -> Forest<T> {
+        let old = self.split_off( 0 );
+        self.append( forest );
+        old
+    }
+
+    /// Returns `true` if both nodes are equal when the order of children is ignored at every level,
+    /// i.e. children are compared as multisets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let a = tr(1) /( tr(2)/tr(3)/tr(4) ) /tr(2);
+    /// let b = tr(1) /tr(2) /( tr(2)/tr(4)/tr(3) );
+    /// assert!( a.eq_unordered( &b ));
+    /// assert!( !a.eq_unordered( &( tr(1) /( tr(2)/tr(3) ) /( tr(2)/tr(4) ))));
+    /// ```
+    pub fn eq_unordered( &self, other: &Node<T> ) -> bool
+        where T: Ord
+    {
+        let mut ids = CanonicalIds::new();
+        self.node_count() == other.node_count() && self.canonical_id( &mut ids ) == other.canonical_id( &mut ids )
+    }
+
+    /// Formats the node and its descendants like `Display` does, but with custom brackets around children and separator between siblings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.format_with( "( ", " )", " " ), tree.to_string() );
+    /// assert_eq!( tree.format_with( "[", "]", ", " ), "0[1[2, 3], 4]" );
+    /// assert_eq!( tr(0).format_with( "[", "]", ", " ), "0" );
+    /// ```
+    pub fn format_with( &self, open: &str, close: &str, sep: &str ) -> String
+        where T: Display
+    {
+        use fmt::Write;
+
+        let mut s = String::new();
+        let mut write_data = |s: &mut String, data: &T| write!( s, "{}", data ).unwrap();
+        write_data( &mut s, self.data() );
+        self.format_children_with( &mut s, open, close, sep, &mut write_data );
+        s
+    }
+
+    /// Formats the node and its descendants like `Display` does, but quotes the data that could not be parsed back otherwise.
+    ///
+    /// Data that is empty, contains whitespace, `(` or `)`, or starts with `"`, is written in double quotes,
+    /// with `"` and `\` inside escaped by a backslash. The output round-trips through `Tree::<String>::try_from()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    /// use std::convert::TryFrom;
+    ///
+    /// let tree = tr( "hello (world)".to_owned() ) /tr( "a".to_owned() ) /tr( String::new() );
+    /// assert_eq!( tree.to_string_escaped(), r#""hello (world)"( a "" )"# );
+    /// assert_eq!( Tree::try_from( tree.to_string_escaped() ).unwrap(), tree );
+    /// ```
+    pub fn to_string_escaped( &self ) -> String
+        where T: Display
+    {
+        let mut s = String::new();
+        write_escaped( &mut s, self.data() );
+        self.format_children_with( &mut s, "( ", " )", " ", &mut write_escaped );
+        s
+    }
+
+    // Appends children to `s` in the format of `format_with()`, or nothing if there is no child.
+    // Data are appended by `write_data`.
+    pub(crate) fn format_children_with<F>( &self, s: &mut String, open: &str, close: &str, sep: &str, write_data: &mut F )
+        where F: FnMut( &mut String, &T )
+    {
+        if self.has_no_child() {
+            return;
+        }
+        s.push_str( open );
+        let mut iters = vec![ self.iter() ];
+        let mut first = true;
+        while let Some( iter ) = iters.last_mut() {
+            match iter.next() {
+                Some( child ) => {
+                    if !first {
+                        s.push_str( sep );
+                    }
+                    write_data( s, child.data() );
+                    if child.has_no_child() {
+                        first = false;
+                    } else {
+                        s.push_str( open );
+                        iters.push( child.iter() );
+                        first = true;
+                    }
+                },
+                None => {
+                    iters.pop();
+                    s.push_str( close );
+                    first = false;
+                },
+            }
+        }
+    }
+
+    // Creates a structurally identical tree of the subtree, as `Tree::try_map()` does.
+    pub(crate) fn try_map_subtree<U,E,F>( &self, mut f: F ) -> Result<Tree<U>,E>
+        where F: FnMut( &T ) -> Result<U,E>
+    {
+        let mut stack = vec![ (self.iter(), Tree::new( f( self.data() )? )) ];
+        loop {
+            let next = stack.last_mut().unwrap().0.next();
+            match next {
+                Some( node ) => stack.push(( node.iter(), Tree::new( f( node.data() )? ))),
+                None => {
+                    let (_, tree) = stack.pop().unwrap();
+                    match stack.last_mut() {
+                        Some( (_, parent) ) => parent.push_back( tree ),
+                        None => return Ok( tree ),
+                    }
+                },
+            }
+        }
+    }
+
+    // Returns the id of the subtree in `ids`, shared by all the subtrees registered there
+    // which are equal to it up to the order of children, registering it if there is none.
+    // A subtree is keyed by its root's data and its children's ids in sorted order, computed in post-order with an explicit stack.
+    pub(crate) fn canonical_id<'a>( &'a self, ids: &mut CanonicalIds<'a,T> ) -> usize
+        where T: Ord
+    {
+        let mut stack = vec![ (self, self.iter(), Vec::with_capacity( self.degree() )) ];
+        loop {
+            let next = stack.last_mut().unwrap().1.next();
+            match next {
+                Some( child ) => stack.push( (child, child.iter(), Vec::with_capacity( child.degree() ))),
+                None => {
+                    let (node, _, mut children) = stack.pop().unwrap();
+                    children.sort_unstable();
+                    let len = ids.len();
+                    let id = *ids.entry( (node.data(), children) ).or_insert( len );
+                    match stack.last_mut() {
+                        Some( (_, _, siblings) ) => siblings.push( id ),
+                        None => return id,
+                    }
+                },
+            }
+        }
+    }
+
+    // Checks the links and cached sizes of all the nodes in the subtree.
+    // A node reachable twice would either break some `prev`/`up` link or exceed its parent's degree,
+    // so counting children against `degree` is enough to detect cycles.
+    pub(crate) fn validate( &self ) -> Result<(), Error> {
+        let mut nodes = vec![ self.non_null() ];
+        while let Some( node ) = nodes.pop() {
+            let node = unsafe{ node.as_ref() };
+            let mut degree = 0;
+            let mut descendants = 0;
+            let mut prev = None;
+            let mut curr = node.head;
+            while let Some( child ) = curr {
+                degree += 1;
+                if degree > node.size.degree {
+                    return Err( "more children linked than the cached degree, or siblings form a cycle".into() );
+                }
+                let child = unsafe{ child.as_ref() };
+                if child.up != Some( node.non_null() ) {
+                    return Err( "child's `up` does not point to its parent".into() );
+                }
+                if child.prev != prev {
+                    return Err( "child's `prev` does not point to its previous sibling".into() );
+                }
+                descendants += child.size.descendants + 1;
+                nodes.push( child.non_null() );
+                prev = Some( child.non_null() );
+                curr = child.next;
+            }
+            if node.tail != prev {
+                return Err( "`tail` does not point to the last child".into() );
+            }
+            if degree != node.size.degree {
+                return Err( "fewer children linked than the cached degree".into() );
+            }
+            if descendants != node.size.descendants {
+                return Err( "cached descendant count does not match the children".into() );
+            }
+        }
+        Ok(())
+    }
+
+    // Locates the descendant node by child indices, starting from `self`.
+    pub(crate) fn locate( &self, path: &[usize] ) -> Option<NonNull<Node<T>>> {
+        let mut node = self.non_null();
+        for &index in path {
+            node = unsafe{ node.as_ref().iter().nth( index )?.non_null() };
+        }
+        Some( node )
+    }
+
+    // Exchanges the positions of two distinct nodes which are not ancestors of each other,
+    // together with their descendants.
+    pub(crate) unsafe fn swap_positions( mut x: NonNull<Node<T>>, mut y: NonNull<Node<T>> ) {
+        let count_x = x.as_ref().node_count();
+        let count_y = y.as_ref().node_count();
+        let mut up_x = x.as_ref().up.unwrap();
+        let mut up_y = y.as_ref().up.unwrap();
+        up_x.as_mut().dec_sizes( 0, count_x );
+        up_y.as_mut().dec_sizes( 0, count_y );
+
+        if y.as_ref().next == Some( x ) {
+            mem::swap( &mut x, &mut y );
+        }
+        if x.as_ref().next == Some( y ) {
+            let prev = x.as_ref().prev;
+            let next = y.as_ref().next;
+            y.as_mut().prev = prev;
+            y.as_mut().connect_next( x.as_mut() );
+            x.as_mut().next = next;
+        } else {
+            let (x, y) = (x.as_mut(), y.as_mut());
+            mem::swap( &mut x.prev, &mut y.prev );
+            mem::swap( &mut x.next, &mut y.next );
+            mem::swap( &mut x.up,   &mut y.up   );
+        }
+        for mut node in [x, y].iter().copied() {
+            let node = node.as_mut();
+            match node.prev {
+                Some( mut prev ) => prev.as_mut().next = Some( node.non_null() ),
+                None => node.up.unwrap().as_mut().head = Some( node.non_null() ),
+            }
+            match node.next {
+                Some( mut next ) => next.as_mut().prev = Some( node.non_null() ),
+                None => node.up.unwrap().as_mut().tail = Some( node.non_null() ),
+            }
+        }
+
+        up_x.as_mut().inc_sizes( 0, count_y );
+        up_y.as_mut().inc_sizes( 0, count_x );
+    }
+
+    // Reverses the order of the children, leaving their own children untouched.
+    pub(crate) fn reverse_children( &mut self ) {
+        mem::swap( &mut self.head, &mut self.tail );
+        let mut child = self.head;
+        while let Some( mut node ) = child {
+            unsafe {
+                let node = node.as_mut();
+                mem::swap( &mut node.prev, &mut node.next );
+                child = node.next;
+            }
+        }
+    }
+
     pub(crate) fn non_null( &self ) -> NonNull<Node<T>> {
         unsafe{ NonNull::new_unchecked( self as *const _ as *mut Node<T> )}
     }
@@ -597,10 +1261,252 @@ impl<T> Node<T> {
     }
 }
 
+/// Canonical ids of subtrees keyed by their roots' data and their children's sorted ids, see `Node::canonical_id`.
+pub(crate) type CanonicalIds<'a,T> = BTreeMap<(&'a T, Vec<usize>), usize>;
+
 impl_debug_display_for_node!( Node, iter, data() );
 impl_order_relations_for_node!( Node, iter, data() );
 impl_hash_for_node!( Node, iter, data() );
 
+// Appends the data to `s`, quoted and escaped if it contains delimiters of the text format of trees.
+pub(crate) fn write_escaped<T:Display>( s: &mut String, data: &T ) {
+    let text = data.to_string();
+    let needs_quotes = text.is_empty()
+        || text.starts_with( '"' )
+        || text.chars().any( |ch| ch.is_whitespace() || ch == '(' || ch == ')' );
+    if needs_quotes {
+        s.push( '"' );
+        for ch in text.chars() {
+            if ch == '"' || ch == '\\' {
+                s.push( '\\' );
+            }
+            s.push( ch );
+        }
+        s.push( '"' );
+    } else {
+        s.push_str( &text );
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test] fn dissolve_child() {
+        use crate::tr;
+
+        let mut tree = tr('0') /tr('1') /( tr('2')/tr('a')/( tr('b')/tr('c') ) ) /tr('3');
+        assert_eq!( tree.root_mut().dissolve_child(1), Some('2') );
+        assert_eq!( tree.to_string(), "0( 1 a b( c ) 3 )" );
+        assert!( tree.validate().is_ok() );
+        assert_eq!( tree.root_mut().dissolve_child(0), Some('1') );
+        assert_eq!( tree.root_mut().dissolve_child(2), Some('3') );
+        assert_eq!( tree.to_string(), "0( a b( c ) )" );
+        assert_eq!( tree.root_mut().dissolve_child(1), Some('b') );
+        assert_eq!( tree.to_string(), "0( a c )" );
+        assert!( tree.validate().is_ok() );
+        assert_eq!( tree.node_count(), 3 );
+        assert_eq!( tree.root_mut().dissolve_child(2), None );
+        assert_eq!( tree.root_mut().front_mut().unwrap().dissolve_child(0), None );
+
+        let mut forest = -( tr(1)/tr(2)/tr(3) ) -( tr(4)/tr(5) );
+        assert_eq!( forest.dissolve_child(1), Some(4) );
+        assert_eq!( forest.dissolve_child(0), Some(1) );
+        assert_eq!( forest, -tr(2)-tr(3)-tr(5) );
+        assert!( forest.validate().is_ok() );
+    }
+
+    #[test] fn update_preorder() {
+        use crate::tr;
+
+        let mut tree = tr(1) /( tr(2)/tr(3) );
+        tree.root_mut().update_preorder( |mut node| *node.data_mut() += 1 );
+        assert_eq!( tree.to_string(), "2( 3( 4 ) )" );
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /( tr(4)/tr(5) ) /tr(6);
+        let mut visited = Vec::new();
+        tree.root_mut().front_mut().unwrap().update_preorder( |node| visited.push( *node.data() ));
+        assert_eq!( visited, vec![ 1, 2, 3 ]);
+
+        let mut visited = Vec::new();
+        tree.root_mut().update_preorder( |mut node| {
+            visited.push( *node.data() );
+            match *node.data() {
+                1 => { node.pop_front(); node.push_back( tr(7)/tr(8) ); },
+                4 => { node.insert_next_sib( tr(9) ); drop( node.detach() ); },
+                _ => (),
+            }
+        });
+        assert_eq!( visited, vec![ 0, 1, 3, 7, 8, 4, 6 ]);
+        assert_eq!( tree.to_string(), "0( 1( 3 7( 8 ) ) 9 6 )" );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn update_preorder_restructuring() {
+        use crate::tr;
+
+        let make = || tr(0) /( tr(1)/tr(2) ) /( tr(3)/tr(4) ) /( tr(5)/tr(6) );
+        let run = |tree: &mut Tree<i32>, f: &mut dyn FnMut( Pin<&mut Node<i32>> )| {
+            let mut visited = Vec::new();
+            tree.root_mut().update_preorder( |node| { visited.push( *node.data() ); f( node ); });
+            assert!( tree.validate().is_ok() );
+            visited
+        };
+
+        for &detached in &[ 1, 3, 5 ] {
+            let mut tree = make();
+            let visited = run( &mut tree, &mut |mut node| if *node.data() == detached {
+                drop( node.detach() );
+            });
+            assert_eq!( visited, (0..7).filter( |&data| data != detached+1 ).collect::<Vec<_>>() );
+            assert_eq!( tree.node_count(), 5 );
+        }
+
+        let mut tree = make();
+        let visited = run( &mut tree, &mut |mut node| match *node.data() {
+            1 => { node.insert_prev_sib( tr(7) ); drop( node.detach() ); },
+            5 => { node.insert_next_sib( tr(8)/tr(9) ); node.insert_prev_sib( tr(10) ); drop( node.detach() ); },
+            _ => (),
+        });
+        assert_eq!( visited, vec![ 0, 1, 3, 4, 5 ]);
+        assert_eq!( tree.to_string(), "0( 7 3( 4 ) 10 8( 9 ) )" );
+
+        let mut tree = make();
+        let visited = run( &mut tree, &mut |mut node| match *node.data() {
+            0 => { node.set_children( -( tr(11)/tr(12) ) -tr(13) ); },
+            11 => { node.set_children( -tr(14)-tr(15) ); },
+            _ => (),
+        });
+        assert_eq!( visited, vec![ 0, 11, 14, 15, 13 ]);
+        assert_eq!( tree.to_string(), "0( 11( 14 15 ) 13 )" );
+        assert_eq!( tree.node_count(), 5 );
+
+        let mut tree = make();
+        let visited = run( &mut tree, &mut |mut node| if *node.data() == 3 {
+            node.insert_next_sib( tr(16) );
+            let children = node.set_children( -tr(17) );
+            drop( node.detach() );
+            drop( children );
+        });
+        assert_eq!( visited, vec![ 0, 1, 2, 3, 5, 6 ]);
+        assert_eq!( tree.to_string(), "0( 1( 2 ) 16 5( 6 ) )" );
+    }
+
+    #[test] fn detach_middle_child() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1) /( tr(2)/tr(3) ) /tr(4);
+        let detached = tree.root_mut().child_mut(1).unwrap().detach();
+        assert_eq!( detached.to_string(), "2( 3 )" );
+        assert_eq!( detached.parent(), None );
+        assert_eq!( tree.to_string(), "0( 1 4 )" );
+        assert_eq!( tree.degree(), 2 );
+        assert_eq!( tree.node_count(), 3 );
+        assert!( tree.validate().is_ok() );
+        assert!( detached.validate().is_ok() );
+
+        let detached = tree.root_mut().child_mut(1).unwrap().detach();
+        assert_eq!( detached, tr(4) );
+        assert_eq!( tree.back(), tree.front() );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] #[should_panic( expected = "the root node can not be detached" )] fn detach_root() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1);
+        tree.root_mut().detach();
+    }
+
+    #[test] fn windows() {
+        use crate::tr;
+
+        let tree = tr(0) /tr(1)/( tr(2)/tr(5) )/tr(3)/tr(4);
+        let data = |size| tree.windows( size )
+            .map( |window| window.iter().map( |node| *node.data() ).collect::<Vec<_>>() )
+            .collect::<Vec<_>>();
+        assert_eq!( data(2), vec![ vec![ 1, 2 ], vec![ 2, 3 ], vec![ 3, 4 ]]);
+        assert_eq!( data(1), vec![ vec![1], vec![2], vec![3], vec![4] ]);
+        assert_eq!( data(4), vec![ vec![ 1, 2, 3, 4 ]]);
+        assert!( data(5).is_empty() );
+        assert_eq!( tr(0).windows(1).count(), 0 );
+        assert_eq!( tree.front().unwrap().windows(1).count(), 0 );
+        assert_eq!( tree.child(1).unwrap().windows(2).count(), 0 );
+        assert_eq!( tree.windows( usize::MAX ).count(), 0 );
+    }
+
+    #[test] #[should_panic( expected = "window size must be non-zero" )] fn windows_of_size_zero() {
+        use crate::tr;
+
+        let tree = tr(0) /tr(1);
+        let _ = tree.windows(0);
+    }
+
+    #[test] fn set_children() {
+        use crate::{fr, tr};
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/( tr(3)/tr(4) ) ) /tr(5);
+        let old = tree.root_mut().front_mut().unwrap().set_children( -tr(8)-( tr(9)/tr(10) ) );
+        assert_eq!( tree.to_string(), "0( 1( 8 9( 10 ) ) 5 )" );
+        assert_eq!( old, -tr(2)-( tr(3)/tr(4) ) );
+        assert_eq!( tree.node_count(), 6 );
+        assert!( tree.validate().is_ok() );
+        assert!( old.validate().is_ok() );
+
+        let old = tree.root_mut().set_children( fr() );
+        assert_eq!( tree, tr(0) );
+        assert_eq!( old.node_count(), 5 );
+        assert!( tree.validate().is_ok() );
+        assert!( old.validate().is_ok() );
+        assert_eq!( tree.root_mut().set_children( fr() ), fr() );
+        assert_eq!( tree.node_count(), 1 );
+        assert_eq!( tree.root_mut().set_children( -tr(7) ), fr() );
+        assert_eq!( tree, tr(0)/tr(7) );
+        assert_eq!( tree.node_count(), 2 );
+        assert!( tree.validate().is_ok() );
+
+        let old = tree.root_mut().front_mut().unwrap().set_children( -( tr(8)/tr(9) ) );
+        assert_eq!( old, fr() );
+        assert_eq!( tree.to_string(), "0( 7( 8( 9 ) ) )" );
+        assert_eq!( tree.node_count(), 4 );
+        assert_eq!( tree.root().iter_descendants().len(), 3 );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn following_siblings() {
+        use crate::{Node, tr};
+
+        let forest = -tr(0) -( tr(1)/tr(2)/tr(3)/tr(4) );
+        let sibs = |node: &Node<i32>| node.following_siblings().map( |node| *node.data() ).collect::<Vec<_>>();
+        let parent = forest.back().unwrap();
+        assert_eq!( sibs( parent.front().unwrap() ), vec![ 3, 4 ]);
+        assert_eq!( parent.front().unwrap().following_siblings().len(), 2 );
+        assert_eq!( sibs( parent.child(1).unwrap() ), vec![ 4 ]);
+        assert_eq!( sibs( parent.back().unwrap() ), vec![] );
+        assert_eq!( sibs( forest.front().unwrap() ), vec![ 1 ]);
+    }
+
+    #[test] fn id() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/tr(2) ) /tr(3);
+        let ids = tree.iter_depth().map( |(_, node)| node.id() ).collect::<Vec<_>>();
+        assert_eq!( tree.front().unwrap().front().unwrap().id(), ids[2] );
+        for (i, a) in ids.iter().enumerate() {
+            for (j, b) in ids.iter().enumerate() {
+                assert_eq!( i == j, a == b );
+            }
+        }
+
+        let depths = tree.iter_depth().map( |(depth, node)| (node.id(), depth) ).collect::<BTreeMap<_,_>>();
+        let moved = tree.front_mut().unwrap().detach();
+        assert_eq!( depths[ &moved.front().unwrap().id() ], 2 );
+        let mut forest = -tr(4);
+        forest.push_back( moved );
+        assert_eq!( forest.back().unwrap().id(), ids[1] );
+    }
+}
+
 #[cfg( miri )]
 mod miri_tests {
     #[test] fn has_no_child() {
@@ -613,6 +1519,16 @@ mod miri_tests {
         assert!( !root.has_no_child() );
     }
 
+    #[test] fn is_leaf() {
+        use crate::tr;
+
+        let tree = tr(1) /tr(2)/tr(3);
+        assert!( !tree.root().is_leaf() );
+        assert_eq!( tree.root().degree(), 2 );
+        assert!( tr(5).root().is_leaf() );
+        assert_eq!( tr(5).root().degree(), 0 );
+    }
+
     #[test] fn degree() {
         use crate::Tree;
 
@@ -641,6 +1557,16 @@ mod miri_tests {
         });
     }
 
+    #[test] fn ancestors() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(2)/tr(3) );
+        let deepest = tree.front().unwrap().front().unwrap();
+        let ancestors = deepest.iter_ancestors().map( |node| *node.data() ).collect::<Vec<_>>();
+        assert_eq!( ancestors, vec![ 2, 1 ]);
+        assert_eq!( tree.root().iter_ancestors().next(), None );
+    }
+
     #[test] fn insert_prev_sib() {
         use crate::tr;
 
@@ -665,6 +1591,19 @@ mod miri_tests {
         assert_eq!( forest, fr() );
     }
 
+    #[test] fn split_off() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1)/tr(2)/tr(3);
+        let forest = tree.root_mut().split_off( 1 );
+        assert_eq!( tree.to_string(), "0( 1 )" );
+        assert_eq!( forest.to_string(), "( 2 3 )" );
+        drop( forest );
+        tree.push_back( tr(4) );
+        assert_eq!( tree.root_mut().split_off( 0 ).to_string(), "( 1 4 )" );
+        assert!( tree.has_no_child() );
+    }
+
     #[test] fn iter() {
         use crate::Tree;
 
@@ -756,4 +1695,21 @@ mod miri_tests {
         tree.root_mut().append( forest );
         assert_eq!( tree.to_string(), "0( 1 2 3 4 )" );
     }
+
+    #[test] fn child() {
+        use crate::tr;
+
+        let tree = tr(0) /tr(1)/tr(2)/tr(3);
+        assert_eq!( tree.child(2), Some( tr(3).root() ));
+        assert_eq!( tree.child(3), None );
+    }
+
+    #[test] fn child_mut() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1)/tr(2)/tr(3);
+        *tree.root_mut().child_mut(1).unwrap().data_mut() = 5;
+        assert_eq!( tree.to_string(), "0( 1 5 3 )" );
+        assert!( tree.root_mut().child_mut(3).is_none() );
+    }
 }
@@ -22,6 +22,23 @@ impl<T> NodeVec<T> {
         }
     }
 
+    // Allocates room for `cap` nodes without constructing them, to be filled by `push_node()` in order.
+    pub(crate) fn with_capacity_raw_non_null( cap: usize ) -> NonNull<NodeVec<T>> {
+        unsafe {
+            NonNull::new_unchecked( Box::into_raw( Box::new( NodeVec::<T> {
+                buf: Vec::with_capacity( cap ),
+                ref_cnt: Cell::new( cap ),
+            })))
+        }
+    }
+
+    // Constructs the next node. The buffer must have been allocated with enough capacity, so that nodes never move.
+    pub(crate) fn push_node( &mut self, up: Option<NonNull<Node<T>>>, data: Data<T> ) -> NonNull<Node<T>> {
+        debug_assert!( self.buf.len() < self.buf.capacity() );
+        self.buf.push( Shared::new( RefCell::new( Node{ up, data, ..Node::default() })));
+        self.non_null_node( self.buf.len()-1 )
+    }
+
     pub(crate) fn non_null( &self ) -> NonNull<NodeVec<T>> {
         unsafe {
             NonNull::new_unchecked( self as *const NodeVec<T> as *mut NodeVec<T> )
@@ -168,6 +185,44 @@ impl<T> NodeVec<T> {
         Tuple::preorder_with_size_hint( tuple, &mut f );
     }
 
+    // Constructs the nodes from `(depth, data)` pairs listed in pre-order, the first one being the root of depth 0,
+    // by `push_node()` so that each node is written only once.
+    // If `forest` is true, the pairs are the top-level trees of a forest whose fake root is put in front of them.
+    // The depths must have been checked to increase by at most 1 from one pair to the next.
+    pub(crate) fn construct_from_depths( &mut self, pairs: Vec<(usize,T)>, forest: bool ) {
+        let owner = self.non_null();
+        let offset = if forest { 1 } else { 0 };
+        let mut open = Vec::<(usize, NonNull<Node<T>>)>::new(); // the ancestors of the current node, deepest last.
+        if forest {
+            open.push(( 0, self.push_node( None, Data::PiledNone{ owner })));
+        }
+        for (index, (depth, data)) in pairs.into_iter().enumerate() {
+            let (index, depth) = (index+offset, depth+offset);
+            while open.len() > depth {
+                let (closed, mut node) = open.pop().unwrap();
+                unsafe{ node.as_mut().size.descendants = index - closed - 1; }
+            }
+            let parent = open.last().map( |&(_, parent)| parent );
+            let mut node = self.push_node( parent, Data::Piled{ data, owner });
+            if let Some( mut parent ) = parent {
+                unsafe {
+                    let parent = parent.as_mut();
+                    match parent.tail {
+                        Some( mut tail ) => tail.as_mut().connect_next( node.as_mut() ),
+                        None => parent.head = Some( node ),
+                    }
+                    parent.tail = Some( node );
+                    parent.size.degree += 1;
+                }
+            }
+            open.push(( index, node ));
+        }
+        let len = self.buf.len();
+        for (closed, mut node) in open {
+            unsafe{ node.as_mut().size.descendants = len - closed - 1; }
+        }
+    }
+
     pub(crate) fn decr_ref( owner: NonNull<NodeVec<T>> ) {
         unsafe {
             let node_vec = owner.as_ref();
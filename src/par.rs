@@ -0,0 +1,66 @@
+//! Parallel iterators powered by rayon, enabled by the "rayon" feature.
+
+use rayon::prelude::*;
+
+use crate::rust::*;
+
+use super::{Forest, Node};
+
+// A root node handed to rayon's worker threads, which lives only as long as the parallel iterator of `par_trees()`.
+// The closure only gets a shared reference, which reads the node's data and links
+// and never touches the reference counts of the scattered or piled storage.
+struct Root<T>( NonNull<Node<T>> );
+
+unsafe impl<T:Sync> Send for Root<T> {}
+unsafe impl<T:Sync> Sync for Root<T> {}
+
+impl<T:Sync> Forest<T> {
+    /// Provides a parallel iterator over the results of `f` applied to child `Node`s, i.e. the roots of the top-level trees.
+    ///
+    /// The nodes are only lent to `f`, so that no reference to them can escape to other threads.
+    /// Results of order-preserving adaptors such as `collect()` are deterministic per root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use trees::tr;
+    ///
+    /// let forest = -tr(1) -( tr(2)/tr(3)/tr(4) ) -( tr(5)/tr(6) );
+    /// let counts = forest.par_trees( |node| node.node_count() ).collect::<Vec<_>>();
+    /// assert_eq!( counts, vec![ 1, 3, 2 ]);
+    /// assert_eq!( forest.par_trees( |node| node.node_count() ).sum::<usize>(), forest.node_count() );
+    /// ```
+    pub fn par_trees<'a,R,F>( &'a self, f: F ) -> impl IndexedParallelIterator<Item=R> + 'a
+        where F: Fn( &Node<T> ) -> R + Send + Sync + 'a
+            , R: Send
+    {
+        self.iter()
+            .map( |node| Root( node.non_null() ))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .map( move |root| f( unsafe{ root.0.as_ref() }))
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+
+    #[test] fn par_trees_sum_node_counts() {
+        let mut forest = Forest::new();
+        for i in 0..100 {
+            let mut tree = crate::tr(i);
+            for j in 0..i {
+                tree.push_back( crate::tr(j) );
+            }
+            forest.push_back( tree );
+        }
+        let sum = forest.par_trees( |node| node.node_count() ).sum::<usize>();
+        assert_eq!( sum, forest.node_count() );
+        assert_eq!( sum, (1..=100).sum::<usize>() );
+
+        let data = forest.par_trees( |node| *node.data() ).collect::<Vec<_>>();
+        assert_eq!( data, (0..100).collect::<Vec<_>>() );
+    }
+}
@@ -219,6 +219,19 @@ impl<T> RcNode<T> {
     /// ```
     pub fn has_no_child( &self ) -> bool { self.node_borrow().has_no_child() }
 
+    /// Returns `true` if this `Node` is a leaf, i.e. it has no child node, otherwise `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use trees::{RcNode, tr};
+    ///
+    /// let root = RcNode::from( tr(0)/tr(1) );
+    /// assert!( !root.is_leaf() );
+    /// assert!( root.front().unwrap().is_leaf() );
+    /// ```
+    pub fn is_leaf( &self ) -> bool { self.node_borrow().is_leaf() }
+
     /// Returns the number of subtrees.
     ///
     /// # Examples
@@ -443,9 +456,9 @@ impl<T> RcNode<T> {
     pub fn iter_rc( &self ) -> IterRc<T> {
         let node = self.node_borrow();
         if node.has_no_child() {
-            IterRc::new( None, 0 )
+            IterRc::new( None, None, 0 )
         } else {
-            IterRc::new( node.front().map( |front| front.non_null() ), node.degree() )
+            IterRc::new( node.head, node.tail, node.degree() )
         }
     }
 
@@ -605,6 +618,14 @@ mod miri_tests {
         assert!( root.front().unwrap().has_no_child() );
     }
 
+    #[test] fn is_leaf() {
+        use crate::{RcNode, tr};
+
+        let root = RcNode::from( tr(0)/tr(1) );
+        assert!( !root.is_leaf() );
+        assert!( root.front().unwrap().is_leaf() );
+    }
+
     #[test] fn degree() {
         use crate::{RcNode, Tree};
 
@@ -16,9 +16,23 @@ use crate::TupleTree;
 
 use crate::rust::*;
 
-use super::{Data, Forest, IterMut, Node, NodeVec, heap};
+use super::{BfsMut, Data, DepthIter, Error, Forest, IterMut, Node, NodeVec, PostOrder, heap};
 
 /// Composed of a root `Node` and a list of its child `Node`s.
+///
+/// The alternate format `{:#}` displays one node per line, indented by two spaces per level.
+/// The formatter's width and fill, if given, replace the default indentation.
+///
+/// # Examples
+///
+/// ```
+/// use trees::tr;
+///
+/// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+/// assert_eq!( format!( "{}", tree ), "1( 2( 3 ) 4 )" );
+/// assert_eq!( format!( "{:#}", tree ), "1\n  2\n    3\n  4" );
+/// assert_eq!( format!( "{:-<#1}", tree ), "1\n-2\n--3\n-4" );
+/// ```
 pub struct Tree<T>{
     pub(crate) root : NonNull<Node<T>>,
     pub(crate) mark : PhantomData<Node<T>>,
@@ -33,6 +47,41 @@ impl<T> Tree<T> {
         }
     }
 
+    /// Creates a `Tree` with the root node associated with given data,
+    /// and the iterator's trees as its children in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::with_children( 0, (1..4).map( tr ));
+    /// assert_eq!( tree, tr(0) /tr(1)/tr(2)/tr(3) );
+    /// ```
+    pub fn with_children<I>( data: T, children: I ) -> Tree<T>
+        where I: IntoIterator<Item=Tree<T>>
+    {
+        let mut tree = Tree::new( data );
+        children.into_iter().for_each( |child| tree.push_back( child ));
+        tree
+    }
+
+    /// Constructs a tree of the given root data and `n` children, the data of the `i`-th child being `f(i)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// assert_eq!( Tree::generate( 0, 3, |i| i+1 ), tr(0) /tr(1) /tr(2) /tr(3) );
+    /// assert_eq!( Tree::generate( 'a', 0, |_| 'b' ), tr('a') );
+    /// ```
+    pub fn generate<F>( data: T, n: usize, f: F ) -> Tree<T>
+        where F: FnMut( usize ) -> T
+    {
+        Tree::with_children( data, (0..n).map( f ).map( Tree::new ))
+    }
+
     /// Constructs tree from tuple notations.
     ///
     /// # Examples
@@ -54,6 +103,465 @@ impl<T> Tree<T> {
         Tree::from_node( unsafe{ node_vec.as_ref().non_null_node(0) })
     }
 
+    /// Constructs tree from `(depth, data)` pairs listed in pre-order, as produced by indentation-based formats.
+    ///
+    /// Returns `None` if the first pair is not of depth 0, if any other pair is of depth 0,
+    /// or if the depth increases by more than 1 from one pair to the next.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::from_depths( vec![ (0,'a'), (1,'b'), (2,'c'), (1,'d') ]);
+    /// assert_eq!( tree, Some( tr('a') /( tr('b')/tr('c') ) /tr('d') ));
+    ///
+    /// assert_eq!( Tree::from_depths( vec![ (0,'a'), (2,'b') ]), None );
+    /// assert_eq!( Tree::from_depths( vec![ (1,'a') ]), None );
+    /// assert_eq!( Tree::<char>::from_depths( vec![] ), None );
+    /// ```
+    pub fn from_depths<I>( iter: I ) -> Option<Tree<T>>
+        where I: IntoIterator<Item=(usize,T)>
+    {
+        let mut iter = iter.into_iter();
+        let (depth, data) = iter.next()?;
+        if depth != 0 {
+            return None;
+        }
+
+        let mut stack = vec![ Tree::new( data )];
+        for (depth, data) in iter {
+            if depth == 0 || depth > stack.len() {
+                return None;
+            }
+            while stack.len() > depth {
+                let child = stack.pop().unwrap();
+                stack.last_mut().unwrap().push_back( child );
+            }
+            stack.push( Tree::new( data ));
+        }
+        while stack.len() > 1 {
+            let child = stack.pop().unwrap();
+            stack.last_mut().unwrap().push_back( child );
+        }
+        stack.pop()
+    }
+
+    /// Constructs tree from `(depth, data)` pairs listed in pre-order like [`from_depths`](#method.from_depths) does,
+    /// but allocates all the nodes at once in one contiguous buffer, as `from_tuple()` does, instead of one allocation per node.
+    ///
+    /// The buffer is freed when the last of its nodes is dropped, so nodes detached from the tree keep it alive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::from_depths_in_arena( vec![ (0,'a'), (1,'b'), (2,'c'), (1,'d') ]);
+    /// assert_eq!( tree, Some( tr('a') /( tr('b')/tr('c') ) /tr('d') ));
+    ///
+    /// assert_eq!( Tree::from_depths_in_arena( vec![ (0,'a'), (2,'b') ]), None );
+    /// assert_eq!( Tree::from_depths_in_arena( vec![ (0,'a'), (0,'b') ]), None );
+    /// assert_eq!( Tree::<char>::from_depths_in_arena( vec![] ), None );
+    /// ```
+    pub fn from_depths_in_arena<I>( iter: I ) -> Option<Tree<T>>
+        where I: IntoIterator<Item=(usize,T)>
+    {
+        let pairs = iter.into_iter().collect::<Vec<_>>();
+        if pairs.first().map( |pair| pair.0 ) != Some(0) || pairs.windows(2).any( |w| w[1].0 == 0 || w[1].0 > w[0].0+1 ) {
+            return None;
+        }
+        let mut node_vec = NodeVec::with_capacity_raw_non_null( pairs.len() );
+        unsafe{ node_vec.as_mut().construct_from_depths( pairs, false )};
+
+        Some( Tree::from_node( unsafe{ node_vec.as_ref().non_null_node(0) }))
+    }
+
+    /// Consumes the tree and returns the data of all its nodes in pre-order.
+    /// Nodes are freed as soon as their data has been moved out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.into_vec(), vec![ 1, 2, 3, 4 ]);
+    ///
+    /// let tree = Tree::<i32>::from_tuple(( 1, (2,3), 4 ));
+    /// assert_eq!( tree.into_vec(), vec![ 1, 2, 3, 4 ]);
+    /// ```
+    pub fn into_vec( self ) -> Vec<T> {
+        let mut vec = Vec::with_capacity( self.node_count() );
+        let mut stack = vec![ self ];
+        while let Some( mut tree ) = stack.pop() {
+            while let Some( child ) = tree.pop_back() {
+                stack.push( child );
+            }
+            vec.push( tree.into_data() );
+        }
+        vec
+    }
+
+    /// Returns the cloned data of all nodes in pre-order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.to_vec(), vec![ 1, 2, 3, 4 ]);
+    /// assert_eq!( tree.to_string(), "1( 2( 3 ) 4 )" );
+    /// ```
+    pub fn to_vec( &self ) -> Vec<T>
+        where T: Clone
+    {
+        let mut vec = Vec::with_capacity( self.node_count() );
+        vec.push( self.data().clone() );
+        let mut stack = vec![ self.iter() ];
+        while let Some( iter ) = stack.last_mut() {
+            match iter.next() {
+                Some( node ) => {
+                    vec.push( node.data().clone() );
+                    stack.push( node.iter() );
+                },
+                None => { stack.pop(); },
+            }
+        }
+        vec
+    }
+
+    /// Returns references to the data of all leaf nodes in pre-order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(4) ) /tr(3);
+    /// assert_eq!( tree.leaf_data(), vec![ &4, &3 ]);
+    /// assert_eq!( tr(0).leaf_data(), vec![ &0 ]);
+    /// ```
+    pub fn leaf_data( &self ) -> Vec<&T> {
+        self.iter_depth()
+            .filter( |(_, node)| node.has_no_child() )
+            .map( |(_, node)| node.data() )
+            .collect()
+    }
+
+    /// Consumes the tree and returns the data of all leaf nodes in pre-order, dropping the data of the others.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(4) ) /tr(3);
+    /// assert_eq!( tree.into_leaf_data(), vec![ 4, 3 ]);
+    /// ```
+    pub fn into_leaf_data( self ) -> Vec<T> {
+        let mut vec = Vec::new();
+        let mut stack = vec![ self ];
+        while let Some( mut tree ) = stack.pop() {
+            if tree.has_no_child() {
+                vec.push( tree.into_data() );
+            } else {
+                while let Some( child ) = tree.pop_back() {
+                    stack.push( child );
+                }
+            }
+        }
+        vec
+    }
+
+    /// Provides an iterator over the copied data of all nodes in pre-order, lazily.
+    /// Use [`to_vec`](#method.to_vec) to collect them at once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.data_iter().len(), 4 );
+    /// assert_eq!( tree.data_iter().filter( |x| x % 2 == 0 ).sum::<i32>(), 6 );
+    /// ```
+    pub fn data_iter( &self ) -> impl ExactSizeIterator<Item=T> + '_
+        where T: Copy
+    {
+        self.iter_depth().map( |(_, node)| *node.data() )
+    }
+
+    /// Provides an iterator over all the nodes in pre-order, paired with their depths, the root being at depth 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// let depths = tree.iter_depth().map( |(depth, node)| (depth, *node.data()) ).collect::<Vec<_>>();
+    /// assert_eq!( depths, vec![ (0,1), (1,2), (2,3), (1,4) ]);
+    /// ```
+    pub fn iter_depth( &self ) -> DepthIter<'_,T> { DepthIter::new( self.root() )}
+
+    /// Provides an iterator over all the nodes in post-order, i.e. children before their parents, ending with the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) );
+    /// assert_eq!( tree.iter_postorder().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 3, 2, 1 ]);
+    /// ```
+    pub fn iter_postorder( &self ) -> PostOrder<'_,T> { PostOrder::new( self.root() )}
+
+    /// Provides an iterator over all the paths from the root to the leaves, in pre-order of the leaves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// let paths = tree.paths()
+    ///     .map( |path| path.iter().map( |node| *node.data() ).collect::<Vec<_>>() )
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!( paths, vec![ vec![ 1, 2, 3 ], vec![ 1, 4 ]]);
+    /// assert_eq!( tr(0).paths().count(), 1 );
+    /// ```
+    pub fn paths( &self ) -> impl Iterator<Item=Vec<&Node<T>>> + '_ {
+        let root = self.root();
+        let mut path = vec![ root ];
+        let (mut leaf_root, mut iters) = if root.has_no_child() {
+            (Some( vec![ root ]), Vec::new() )
+        } else {
+            (None, vec![ root.iter() ])
+        };
+        iter::from_fn( move || {
+            if let Some( path ) = leaf_root.take() {
+                return Some( path );
+            }
+            loop {
+                match iters.last_mut()?.next() {
+                    Some( child ) => {
+                        path.push( child );
+                        iters.push( child.iter() );
+                        if child.has_no_child() {
+                            return Some( path.clone() );
+                        }
+                    },
+                    None => {
+                        iters.pop();
+                        path.pop();
+                    },
+                }
+            }
+        })
+    }
+
+    /// Returns the number of nodes whose data satisfy the predicate, including the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(4) ) /tr(3);
+    /// assert_eq!( tree.count_where( |x| x % 2 == 0 ), 2 );
+    /// ```
+    pub fn count_where<F>( &self, mut pred: F ) -> usize
+        where F: FnMut( &T ) -> bool
+    {
+        self.iter_depth().filter( |(_, node)| pred( node.data() )).count()
+    }
+
+    /// Returns the node whose data gives the maximum key, the first one in pre-order if several are equally maximum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(5)/tr(3) ) /tr(2);
+    /// assert_eq!( tree.max_by_key( |&x| x ).map( |node| *node.data() ), Some(5) );
+    /// assert_eq!( tree.max_by_key( |&x| x % 2 ).map( |node| *node.data() ), Some(1) );
+    /// ```
+    pub fn max_by_key<K,F>( &self, f: F ) -> Option<&Node<T>>
+        where K: Ord, F: FnMut( &T ) -> K
+    {
+        self.extreme_by_key( f, Ordering::Greater )
+    }
+
+    /// Returns the node whose data gives the minimum key, the first one in pre-order if several are equally minimum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(5)/tr(3) ) /tr(2);
+    /// assert_eq!( tree.min_by_key( |&x| x ).map( |node| *node.data() ), Some(1) );
+    /// assert_eq!( tree.min_by_key( |&x| x % 2 ).map( |node| *node.data() ), Some(2) );
+    /// ```
+    pub fn min_by_key<K,F>( &self, f: F ) -> Option<&Node<T>>
+        where K: Ord, F: FnMut( &T ) -> K
+    {
+        self.extreme_by_key( f, Ordering::Less )
+    }
+
+    // Returns the first node in pre-order whose key is not beaten by any other node's key in the direction of `wanted`.
+    fn extreme_by_key<K,F>( &self, mut f: F, wanted: Ordering ) -> Option<&Node<T>>
+        where K: Ord, F: FnMut( &T ) -> K
+    {
+        let mut best: Option<(K, &Node<T>)> = None;
+        for (_, node) in self.iter_depth() {
+            let key = f( node.data() );
+            match best {
+                Some(( ref best_key, _ )) if key.cmp( best_key ) != wanted => (),
+                _ => best = Some(( key, node )),
+            }
+        }
+        best.map( |(_, node)| node )
+    }
+
+    /// Creates a structurally identical tree by calling the fallible closure on each node's data.
+    /// Stops at the first error and returns it, dropping the partially built tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr("1") /( tr("2")/tr("3") );
+    /// assert_eq!( tree.try_map( |s| s.parse::<i32>() ), Ok( tr(1) /( tr(2)/tr(3) )));
+    ///
+    /// let tree = tr("1") /tr("x");
+    /// assert!( tree.try_map( |s| s.parse::<i32>() ).is_err() );
+    /// ```
+    pub fn try_map<U,E,F>( &self, f: F ) -> Result<Tree<U>,E>
+        where F: FnMut( &T ) -> Result<U,E>
+    {
+        self.root().try_map_subtree( f )
+    }
+
+    /// Reverses the order of children at every node, mirroring the whole tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(1) /( tr(2)/tr(4)/tr(5) ) /tr(3);
+    /// tree.reverse_all();
+    /// assert_eq!( tree.to_string(), "1( 3 2( 5 4 ) )" );
+    /// ```
+    pub fn reverse_all( &mut self ) {
+        let mut stack = vec![ self.root ];
+        while let Some( mut node ) = stack.pop() {
+            unsafe {
+                node.as_mut().reverse_children();
+                stack.extend( node.as_ref().iter().map( Node::non_null ));
+            }
+        }
+    }
+
+    /// Calls `f` on every node in pre-order, so that a node is updated before its children.
+    /// See [`Node::update_preorder`](../node/struct.Node.html#method.update_preorder) for more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(1) /( tr(2)/tr(3) );
+    /// tree.update_preorder( |mut node| *node.data_mut() += 1 );
+    /// assert_eq!( tree.to_string(), "2( 3( 4 ) )" );
+    /// ```
+    pub fn update_preorder<F>( &mut self, f: F )
+        where F: FnMut( Pin<&mut Node<T>> )
+    {
+        self.root_mut_().update_preorder( f );
+    }
+
+    /// Calls `f` on every node in post-order, so that the children of a node have been updated when `f` is called on it.
+    ///
+    /// The traversal is iterative, so it does not overflow the stack on deep trees.
+    /// The successor of a node is located before calling `f` on it, so that `f` may restructure the node's descendants,
+    /// or even detach the node, without affecting the nodes to be visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(1) /( tr(2)/tr(3)/tr(4) ) /tr(5);
+    /// tree.update_postorder( |mut node| {
+    ///     let sum = node.iter().map( |child| *child.data() ).sum::<i32>();
+    ///     *node.data_mut() += sum;
+    /// });
+    /// assert_eq!( tree.to_string(), "15( 9( 3 4 ) 5 )" );
+    /// ```
+    pub fn update_postorder<F>( &mut self, mut f: F )
+        where F: FnMut( Pin<&mut Node<T>> )
+    {
+        let root = self.root;
+        let mut curr = PostOrder::leftmost_leaf( root );
+        loop {
+            let next = if curr == root {
+                None
+            } else {
+                let node = unsafe{ curr.as_ref() };
+                Some( match node.next {
+                    Some( next ) => PostOrder::leftmost_leaf( next ),
+                    None => node.up.unwrap(),
+                })
+            };
+            f( unsafe{ Pin::new_unchecked( &mut *curr.as_ptr() )});
+            match next {
+                Some( next ) => curr = next,
+                None => break,
+            }
+        }
+    }
+
+    /// Returns `true` if, at every node, the heights of any two child subtrees differ by at most 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// assert!( ( tr(0) /( tr(1)/tr(2) ) /tr(3) ).is_height_balanced() );
+    /// assert!( !( tr(0) /( tr(1)/( tr(2)/tr(3) )) /tr(4) ).is_height_balanced() );
+    /// ```
+    pub fn is_height_balanced( &self ) -> bool {
+        // Each entry keeps the children yet to visit and the (min, max) height of the visited ones.
+        let mut stack = vec![ (self.iter(), None::<(usize,usize)>) ];
+        loop {
+            let next = stack.last_mut().unwrap().0.next();
+            match next {
+                Some( node ) => stack.push(( node.iter(), None )),
+                None => {
+                    let height = match stack.pop().unwrap().1 {
+                        Some( (min, max) ) if max - min > 1 => return false,
+                        Some( (_, max) ) => max + 1,
+                        None => 1,
+                    };
+                    match stack.last_mut() {
+                        Some( (_, heights) ) => *heights = Some( match *heights {
+                            Some( (min, max) ) => (min.min( height ), max.max( height )),
+                            None => (height, height),
+                        }),
+                        None => return true,
+                    }
+                },
+            }
+        }
+    }
+
     pub(crate) fn into_data( mut self ) -> T {
         let value = self.root_mut_().data.replace( Data::None ).into_inner();
         mem::forget( self );
@@ -86,6 +594,19 @@ impl<T> Tree<T> {
     /// ```
     pub fn iter_mut<'a, 's:'a>( &'s mut self ) -> IterMut<'a,T> { self.root_mut_().iter_mut() }
 
+    /// Provides a mutable iterator over the data of all nodes in level order, each paired with its level, the root being at level 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /( tr(0)/tr(0) ) /tr(0);
+    /// tree.iter_mut_bfs().for_each( |(level, data)| *data = level );
+    /// assert_eq!( tree.to_string(), "0( 1( 2 ) 1 )" );
+    /// ```
+    pub fn iter_mut_bfs( &mut self ) -> BfsMut<'_,T> { BfsMut::new( Some( self.root ), self.node_count() )}
+
     /// Adds the tree as the first child.
     ///
     /// # Examples
@@ -118,44 +639,306 @@ impl<T> Tree<T> {
         self.root_mut_().push_back( tree );
     }
 
-    /// Adds all the forest's trees at front of children list.
+    /// Adds all the forest's trees at front of children list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Forest, Tree};
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.push_back( Tree::new(1) );
+    /// tree.push_back( Tree::new(2) );
+    /// let mut forest = Forest::new();
+    /// forest.push_back( Tree::new(3) );
+    /// forest.push_back( Tree::new(4) );
+    /// tree.prepend( forest );
+    /// assert_eq!( tree.to_string(), "0( 3 4 1 2 )" );
+    /// ```
+    pub fn prepend( &mut self, forest: Forest<T> ) {
+        self.root_mut_().prepend( forest );
+    }
+
+    /// Adds all the forest's trees at back of children list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Forest, Tree};
+    ///
+    /// let mut tree = Tree::new(0);
+    /// tree.push_back( Tree::new(1) );
+    /// tree.push_back( Tree::new(2) );
+    /// let mut forest = Forest::new();
+    /// forest.push_back( Tree::new(3) );
+    /// forest.push_back( Tree::new(4) );
+    /// tree.root_mut().append( forest );
+    /// assert_eq!( tree.to_string(), "0( 1 2 3 4 )" );
+    /// ```
+    pub fn append( &mut self, forest: Forest<T> ) {
+        self.root_mut_().append( forest );
+    }
+
+    /// Exchanges the subtrees addressed by the paths `a` and `b`, each of which is a list of child indices
+    /// starting from the root. Only the links between nodes are changed, no data is moved.
+    ///
+    /// Returns `false` and does nothing if either path is invalid, or if one subtree contains the other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /( tr(4)/tr(5) );
+    /// assert!( tree.swap( &[0,1], &[1,0] ));
+    /// assert_eq!( tree.to_string(), "0( 1( 2 5 ) 4( 3 ) )" );
+    /// assert!( tree.swap( &[0], &[1] ));
+    /// assert_eq!( tree.to_string(), "0( 4( 3 ) 1( 2 5 ) )" );
+    ///
+    /// assert!( !tree.swap( &[0], &[0,0] ));
+    /// assert!( !tree.swap( &[0], &[2] ));
+    /// assert_eq!( tree.to_string(), "0( 4( 3 ) 1( 2 5 ) )" );
+    /// ```
+    pub fn swap( &mut self, a: &[usize], b: &[usize] ) -> bool {
+        let (x, y) = match ( self.root().locate( a ), self.root().locate( b )) {
+            (Some( x ), Some( y )) => (x, y),
+            _ => return false,
+        };
+        if a == b {
+            return true;
+        }
+        if a.starts_with( b ) || b.starts_with( a ) {
+            return false;
+        }
+        unsafe{ Node::swap_positions( x, y ); }
+        true
+    }
+
+    /// Returns the lowest common ancestor of the nodes located by paths `a` and `b`,
+    /// which is the node at their common path prefix.
+    /// A node is regarded as an ancestor of itself.
+    /// Returns `None` if either path does not locate a node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.lca( &[0,0], &[0,1] ).map( |node| *node.data() ), Some(1) );
+    /// assert_eq!( tree.lca( &[0,1], &[1] ).map( |node| *node.data() ), Some(0) );
+    /// assert_eq!( tree.lca( &[0], &[0,1] ).map( |node| *node.data() ), Some(1) );
+    /// assert_eq!( tree.lca( &[0], &[2] ), None );
+    /// ```
+    pub fn lca( &self, a: &[usize], b: &[usize] ) -> Option<&Node<T>> {
+        self.root().locate( a )?;
+        self.root().locate( b )?;
+        let common = a.iter().zip( b ).take_while( |(x,y)| x == y ).count();
+        self.root().locate( &a[ ..common ]).map( |node| unsafe{ &*node.as_ptr() })
+    }
+
+    /// Compares the node counts of two trees first, and then the trees themselves with `Ord::cmp()` if the counts are equal.
+    ///
+    /// Useful for sorting trees by size while keeping a total order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!( ( tr(9)/tr(9) ).cmp_by_size( &( tr(1)/tr(1)/tr(1) )), Ordering::Less );
+    /// assert_eq!( ( tr(2)/tr(1) ).cmp_by_size( &( tr(1)/tr(2) )), Ordering::Greater );
+    /// ```
+    pub fn cmp_by_size( &self, other: &Self ) -> Ordering
+        where T: Ord
+    {
+        self.node_count().cmp( &other.node_count() ).then_with( || self.cmp( other ))
+    }
+
+    /// Feeds the shape of the tree into the given `Hasher`, i.e. the number of children of each node in pre-order.
+    /// The data is never hashed, so trees of the same shape produce the same hash regardless of their data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::Hasher;
+    ///
+    /// let shape_hash = |tree: &trees::Tree<i32>| {
+    ///     let mut state = DefaultHasher::new();
+    ///     tree.shape_hash( &mut state );
+    ///     state.finish()
+    /// };
+    /// assert_eq!( shape_hash( &( tr(1)/tr(2)/tr(3) )), shape_hash( &( tr(9)/tr(8)/tr(7) )));
+    /// assert_ne!( shape_hash( &( tr(1)/tr(2)/tr(3) )), shape_hash( &( tr(1)/( tr(2)/tr(3) ))));
+    /// ```
+    pub fn shape_hash<H:Hasher>( &self, state: &mut H ) {
+        for (_, node) in self.iter_depth() {
+            state.write_usize( node.degree() );
+        }
+    }
+
+    /// Walks the whole tree and checks its internal links and cached sizes,
+    /// returning an error describing the first inconsistency found.
+    ///
+    /// Trees built by this crate's safe APIs always pass.
+    /// It is intended for debugging code which manipulates nodes through `unsafe` internals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4);
+    /// assert!( tree.validate().is_ok() );
+    /// ```
+    pub fn validate( &self ) -> Result<(), Error> {
+        if self.root().up.is_some() || self.root().prev.is_some() || self.root().next.is_some() {
+            return Err( "root node is linked to other nodes".into() );
+        }
+        self.root().validate()
+    }
+
+    /// Walks the whole tree following the links to children and siblings, tracking the nodes visited,
+    /// and returns `false` if any node is reached twice.
+    ///
+    /// Trees built by this crate's safe APIs are always acyclic.
+    /// Unlike `validate()`, it does not rely on the cached sizes to terminate,
+    /// so it is suitable for checking structures linked through `unsafe` internals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// assert!( ( tr(0) /( tr(1)/tr(2) ) /tr(3) ).is_acyclic() );
+    /// ```
+    pub fn is_acyclic( &self ) -> bool {
+        let mut visited = BTreeSet::new();
+        visited.insert( self.root );
+        let mut stack = vec![ self.root ];
+        while let Some( node ) = stack.pop() {
+            let mut curr = unsafe{ node.as_ref().head };
+            while let Some( child ) = curr {
+                if !visited.insert( child ) {
+                    return false;
+                }
+                stack.push( child );
+                curr = unsafe{ child.as_ref().next };
+            }
+        }
+        true
+    }
+
+    /// Appends `child` as the last child of the node located by `path`.
+    /// Gives `child` back in `Err` if `path` does not locate a node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr(0) /tr(1);
+    /// assert!( tree.graft( &[0], tr(9) ).is_ok() );
+    /// assert_eq!( tree.to_string(), "0( 1( 9 ) )" );
+    /// assert_eq!( tree.graft( &[1], tr(8) ), Err( tr(8) ));
+    /// ```
+    pub fn graft( &mut self, path: &[usize], child: Tree<T> ) -> Result<(), Tree<T>> {
+        match self.root().locate( path ) {
+            Some( mut node ) => {
+                unsafe{ node.as_mut().push_back( child ); }
+                Ok(())
+            },
+            None => Err( child ),
+        }
+    }
+
+    /// Merges another tree with an equal root into this one, as when building a trie.
+    /// Each of `other`'s children is merged recursively into the first child with equal data, or appended if none matches.
+    ///
+    /// Returns `Err( other )` and does nothing if the roots' data are not equal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let mut tree = tr('a') /( tr('b')/tr('c') );
+    /// assert!( tree.merge( tr('a') /( tr('b')/tr('d') ) /tr('e') ).is_ok() );
+    /// assert_eq!( tree.to_string(), "a( b( c d ) e )" );
+    /// assert_eq!( tree.merge( tr('x') ), Err( tr('x') ));
+    /// ```
+    pub fn merge( &mut self, other: Tree<T> ) -> Result<(), Tree<T>>
+        where T: PartialEq
+    {
+        if self.data() != other.data() {
+            return Err( other );
+        }
+        let mut stack = vec![ (self.root, other) ];
+        while let Some( (mut dest, mut src) ) = stack.pop() {
+            while let Some( child ) = src.pop_front() {
+                let dest = unsafe{ dest.as_mut() };
+                match dest.iter().find( |node| node.data() == child.data() ) {
+                    Some( node ) => stack.push(( node.non_null(), child )),
+                    None => dest.push_back( child ),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the data of the node addressed by `path`, a list of child indices starting from the root,
+    /// and returns the previous data, or `None` if the path is invalid.
     ///
     /// # Examples
     ///
     /// ```
-    /// use trees::{Forest, Tree};
+    /// use trees::tr;
     ///
-    /// let mut tree = Tree::new(0);
-    /// tree.push_back( Tree::new(1) );
-    /// tree.push_back( Tree::new(2) );
-    /// let mut forest = Forest::new();
-    /// forest.push_back( Tree::new(3) );
-    /// forest.push_back( Tree::new(4) );
-    /// tree.prepend( forest );
-    /// assert_eq!( tree.to_string(), "0( 3 4 1 2 )" );
+    /// let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) );
+    /// assert_eq!( tree.replace_data( &[0,1], 9 ), Some(3) );
+    /// assert_eq!( tree.to_string(), "0( 1( 2 9 ) )" );
+    /// assert_eq!( tree.replace_data( &[1], 9 ), None );
     /// ```
-    pub fn prepend( &mut self, forest: Forest<T> ) {
-        self.root_mut_().prepend( forest );
+    pub fn replace_data( &mut self, path: &[usize], data: T ) -> Option<T> {
+        self.root().locate( path )
+            .map( |mut node| mem::replace( unsafe{ node.as_mut().data_mut() }, data ))
     }
 
-    /// Adds all the forest's trees at back of children list.
+    /// Removes every subtree whose root's data satisfies the predicate, returning the number of subtrees removed.
+    /// The root is never tested, and descendants of a removed subtree are not tested either.
     ///
     /// # Examples
     ///
     /// ```
-    /// use trees::{Forest, Tree};
+    /// use trees::tr;
     ///
-    /// let mut tree = Tree::new(0);
-    /// tree.push_back( Tree::new(1) );
-    /// tree.push_back( Tree::new(2) );
-    /// let mut forest = Forest::new();
-    /// forest.push_back( Tree::new(3) );
-    /// forest.push_back( Tree::new(4) );
-    /// tree.root_mut().append( forest );
-    /// assert_eq!( tree.to_string(), "0( 1 2 3 4 )" );
+    /// let mut tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+    /// assert_eq!( tree.prune_if( |&x| x == 2 ), 1 );
+    /// assert_eq!( tree.to_string(), "1( 4 )" );
     /// ```
-    pub fn append( &mut self, forest: Forest<T> ) {
-        self.root_mut_().append( forest );
+    pub fn prune_if<F>( &mut self, mut pred: F ) -> usize
+        where F: FnMut( &T ) -> bool
+    {
+        let mut count = 0;
+        let mut stack = vec![ self.root ];
+        while let Some( node ) = stack.pop() {
+            let mut child = unsafe{ node.as_ref().head };
+            while let Some( mut curr ) = child {
+                unsafe {
+                    child = curr.as_ref().next;
+                    if pred( curr.as_ref().data() ) {
+                        drop( curr.as_mut().detach() );
+                        count += 1;
+                    } else {
+                        stack.push( curr );
+                    }
+                }
+            }
+        }
+        count
     }
 
     /// Removes and returns the given `Tree`'s children.
@@ -182,6 +965,35 @@ impl<T> Tree<T> {
         forest
     }
 
+    /// Consumes the tree, dropping the root's data and returning its children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let forest = ( tr(0) /tr(1)/tr(2) ).into_forest();
+    /// assert_eq!( forest, -tr(1)-tr(2) );
+    /// assert_eq!( Tree::from_forest( 0, forest ), tr(0) /tr(1)/tr(2) );
+    /// ```
+    pub fn into_forest( mut self ) -> Forest<T> { self.abandon() }
+
+    /// Creates a `Tree` with the root node associated with given data, and the forest's trees as its children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::from_forest( 0, -tr(1)-tr(2) );
+    /// assert_eq!( tree.to_string(), "0( 1 2 )" );
+    /// ```
+    pub fn from_forest( data: T, forest: Forest<T> ) -> Tree<T> {
+        let mut tree = Tree::new( data );
+        tree.append( forest );
+        tree
+    }
+
     /// Removes and returns the first child.
     ///
     /// # Examples
@@ -243,7 +1055,12 @@ impl<T> Deref for Tree<T> {
 
 impl<T> Drop for Tree<T> {
     fn drop( &mut self ) {
-        while let Some(_) = self.root_mut_().pop_front() {}
+        // Moves grandchildren up before dropping each child as a leaf, so that deep trees do not overflow the stack.
+        while let Some( mut child ) = self.root_mut_().pop_front() {
+            while let Some( grandchild ) = child.root_mut_().pop_back() {
+                self.root_mut_().push_front( grandchild );
+            }
+        }
         heap::drop_node( self.root );
     }
 }
@@ -261,6 +1078,744 @@ mod tests {
         let piled = Tree::<i32>::from_tuple( tuple );
         assert_eq!( piled.to_string(), "0( 1( 2 3 ) 4( 5 6 ) )" );
     }
+
+    #[test] fn generate() {
+        use crate::tr;
+
+        assert_eq!( Tree::generate( 0, 3, |i| i+1 ), tr(0)/tr(1)/tr(2)/tr(3) );
+        let mut calls = Vec::new();
+        let tree = Tree::generate( 10, 5, |i| { calls.push( i ); i*i });
+        assert_eq!( calls, vec![ 0, 1, 2, 3, 4 ]);
+        assert_eq!( tree.to_string(), "10( 0 1 4 9 16 )" );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn format_with() {
+        use crate::tr;
+
+        let tree = tr(0) /( tr(1)/( tr(2)/tr(3) ) ) /tr(4) /( tr(5)/tr(6)/tr(7) );
+        assert_eq!( tree.format_with( "( ", " )", " " ), tree.to_string() );
+        assert_eq!( tree.format_with( "[", "]", ", " ), "0[1[2[3]], 4, 5[6, 7]]" );
+        assert_eq!( tree.format_with( " (", ")", " " ), "0 (1 (2 (3)) 4 5 (6 7))" );
+    }
+
+    #[test] fn validate() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/tr(3)/tr(4) ) /tr(5);
+        assert!( tree.validate().is_ok() );
+
+        unsafe {
+            let mut node_2 = tree.root().locate( &[0,0] ).unwrap();
+            let node_3 = node_2.as_ref().next;
+            node_2.as_mut().next = Some( node_2 );
+            assert!( tree.validate().is_err() );
+            node_2.as_mut().next = node_3;
+        }
+        assert!( tree.validate().is_ok() );
+
+        unsafe {
+            let mut node_1 = tree.root().locate( &[0] ).unwrap();
+            node_1.as_mut().size.descendants += 1;
+            assert!( tree.validate().is_err() );
+            node_1.as_mut().size.descendants -= 1;
+
+            let mut node_4 = tree.root().locate( &[0,2] ).unwrap();
+            let up = node_4.as_ref().up;
+            node_4.as_mut().up = Some( tree.root().non_null() );
+            assert!( tree.validate().is_err() );
+            node_4.as_mut().up = up;
+        }
+
+        tree.root_mut().front_mut().unwrap().pop_back();
+        tree.front_mut().unwrap().push_front( tr(6)/tr(7) );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn is_acyclic() {
+        use crate::tr;
+
+        let tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4);
+        assert!( tree.is_acyclic() );
+        assert!( tr(0).is_acyclic() );
+
+        unsafe {
+            let mut node_3 = tree.root().locate( &[0,1] ).unwrap();
+            node_3.as_mut().next = tree.root().head;
+            assert!( !tree.is_acyclic() );
+            node_3.as_mut().next = None;
+            assert!( tree.is_acyclic() );
+
+            let mut node_4 = tree.root().locate( &[1] ).unwrap();
+            node_4.as_mut().head = Some( tree.root().non_null() );
+            assert!( !tree.is_acyclic() );
+            node_4.as_mut().head = Some( node_4 );
+            assert!( !tree.is_acyclic() );
+            node_4.as_mut().head = None;
+        }
+        assert!( tree.is_acyclic() );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn with_children() {
+        use crate::tr;
+
+        assert_eq!( Tree::with_children( 0, vec![ tr(1), tr(2) ]), tr(0)/tr(1)/tr(2) );
+        assert_eq!( Tree::with_children( 0, None ), tr(0) );
+
+        let tree = Tree::with_children( 0, vec![ tr(1)/tr(2), Tree::with_children( 3, Some( tr(4) ))]);
+        assert_eq!( tree, tr(0) /( tr(1)/tr(2) ) /( tr(3)/tr(4) ));
+        assert_eq!( tree.node_count(), 5 );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn cached_node_count_matches_walk() {
+        use crate::{TreeWalk, tr, walk::Visit};
+
+        fn walk_count( tree: Tree<i32> ) -> (usize, Tree<i32>) {
+            let mut walk = TreeWalk::from( tree );
+            let mut count = 0;
+            while let Some( visit ) = walk.get() {
+                if let Visit::Begin(_) | Visit::Leaf(_) = visit {
+                    count += 1;
+                }
+                walk.forward();
+            }
+            (count, walk.into())
+        }
+
+        fn check( tree: Tree<i32> ) -> Tree<i32> {
+            let (count, tree) = walk_count( tree );
+            assert_eq!( tree.node_count(), count );
+            for node in tree.root().iter_descendants() {
+                assert_eq!( node.node_count(), walk_count( node.deep_clone() ).0 );
+            }
+            tree
+        }
+
+        let mut tree = check( tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4) );
+        tree.front_mut().unwrap().push_back( tr(5)/tr(6)/tr(7) );
+        let mut tree = check( tree );
+        let popped = tree.front_mut().unwrap().pop_front();
+        assert_eq!( popped, Some( tr(2) ));
+        let mut tree = check( tree );
+        tree.back_mut().unwrap().append( -tr(8) -( tr(9)/tr(10) ));
+        let mut tree = check( tree );
+        let detached = tree.root_mut().front_mut().unwrap().back_mut().unwrap().detach();
+        assert_eq!( detached, tr(5)/tr(6)/tr(7) );
+        let mut tree = check( tree );
+        tree.prune_if( |&data| data == 9 );
+        let tree = check( tree );
+        assert_eq!( tree.to_string(), "0( 1( 3 ) 4( 8 ) )" );
+    }
+
+    #[cfg( not( feature = "no_std" ))]
+    #[test] fn shape_hash() {
+        use crate::tr;
+
+        fn hashes<T:Hash>( tree: &Tree<T> ) -> (u64, u64) {
+            let mut shape = DefaultHasher::default();
+            tree.shape_hash( &mut shape );
+            let mut full = DefaultHasher::default();
+            tree.hash( &mut full );
+            (shape.finish(), full.finish())
+        }
+
+        let (shape_a, full_a) = hashes( &( tr(1)/tr(2)/tr(3) ));
+        let (shape_b, full_b) = hashes( &( tr(9)/tr(8)/tr(7) ));
+        assert_eq!( shape_a, shape_b );
+        assert_ne!( full_a, full_b );
+        assert_eq!( hashes( &( tr("x")/tr("y")/tr("z") )).0, shape_a );
+        assert_ne!( hashes( &( tr(1)/( tr(2)/tr(3) ))).0, shape_a );
+        assert_ne!( hashes( &( tr(1)/tr(2)/tr(3)/tr(4) )).0, shape_a );
+        assert_ne!( hashes( &( tr(0) /( tr(1)/tr(2) ) /tr(3) )).0, hashes( &( tr(0) /tr(1) /( tr(2)/tr(3) ))).0 );
+    }
+
+    #[test] fn cmp_by_size() {
+        use crate::tr;
+
+        let mut trees = vec![ tr(1)/tr(2)/tr(3), tr(9), tr(0)/( tr(0)/tr(0)/tr(0) ), tr(5)/tr(1), tr(1)/tr(1)/tr(4) ];
+        trees.sort_by( Tree::cmp_by_size );
+        assert_eq!( trees, vec![ tr(9), tr(5)/tr(1), tr(1)/tr(1)/tr(4), tr(1)/tr(2)/tr(3), tr(0)/( tr(0)/tr(0)/tr(0) )]);
+        assert_eq!( tr(1).cmp_by_size( &tr(1) ), Ordering::Equal );
+    }
+
+    #[test] fn split_off() {
+        use crate::tr;
+
+        let mut tree = tr(9) /( tr(0)/tr(1)/tr(2)/tr(3) );
+        let forest = tree.front_mut().unwrap().split_off( 1 );
+        assert_eq!( tree.to_string(), "9( 0( 1 ) )" );
+        assert_eq!( forest.to_string(), "( 2 3 )" );
+        assert_eq!( tree.node_count(), 3 );
+        assert!( tree.validate().is_ok() );
+        assert!( forest.validate().is_ok() );
+        assert!( forest.iter().all( |node| node.parent().is_none() ));
+    }
+
+    #[test] fn graft() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1);
+        assert_eq!( tree.graft( &[0], tr(9) ), Ok(()) );
+        assert_eq!( tree.to_string(), "0( 1( 9 ) )" );
+        assert_eq!( tree.graft( &[], tr(2)/tr(3) ), Ok(()) );
+        assert_eq!( tree.graft( &[1,0], tr(4) ), Ok(()) );
+        assert_eq!( tree.graft( &[0,0], tr(5) ), Ok(()) );
+        assert_eq!( tree.to_string(), "0( 1( 9( 5 ) ) 2( 3( 4 ) ) )" );
+        assert_eq!( tree.graft( &[0,1], tr(6)/tr(7) ), Err( tr(6)/tr(7) ));
+        assert_eq!( tree.graft( &[2], tr(6) ), Err( tr(6) ));
+        assert_eq!( tree.node_count(), 7 );
+        assert!( tree.validate().is_ok() );
+    }
+
+    #[test] fn iter_postorder() {
+        use crate::{TreeWalk, tr, walk::Visit};
+
+        let tree = tr(1) /( tr(2)/tr(3) );
+        assert_eq!( tree.iter_postorder().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 3, 2, 1 ]);
+        assert_eq!( tr(0).iter_postorder().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 0 ]);
+
+        let tree = tr(0) /( tr(1)/tr(2)/( tr(3)/tr(4)/tr(5) ) ) /tr(6) /( tr(7)/( tr(8)/tr(9) ) );
+        let mut iter = tree.iter_postorder();
+        assert_eq!( iter.len(), 10 );
+        iter.next();
+        assert_eq!( iter.len(), 9 );
+
+        let mut walk = TreeWalk::from( tree.clone() );
+        let mut expected = Vec::new();
+        while let Some( visit ) = walk.get() {
+            match visit {
+                Visit::Leaf( node ) | Visit::End( node ) => expected.push( *node.data() ),
+                Visit::Begin(_) => (),
+            }
+            walk.forward();
+        }
+        assert_eq!( tree.iter_postorder().map( |node| *node.data() ).collect::<Vec<_>>(), expected );
+        assert_eq!( expected, vec![ 2, 4, 5, 3, 1, 6, 9, 8, 7, 0 ]);
+
+        let subtree = tree.front().unwrap();
+        let subtree = subtree.deep_clone();
+        assert_eq!( subtree.iter_postorder().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 2, 4, 5, 3, 1 ]);
+    }
+
+    #[test] fn count_where() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(2)/tr(4)/( tr(5)/tr(6) ) ) /tr(3);
+        assert_eq!( tree.count_where( |x| x % 2 == 0 ), 3 );
+        assert_eq!( tree.count_where( |_| true ), tree.node_count() );
+        assert_eq!( tree.count_where( |&x| x == 1 ), 1 );
+        assert_eq!( tree.count_where( |&x| x > 6 ), 0 );
+    }
+
+    #[cfg( all( debug_assertions, feature = "check_alias" ))]
+    #[test] #[should_panic( expected = "the tree to insert is already owned by the destination" )]
+    fn push_aliased_child() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/tr(2) );
+        let grandchild = tree.front().unwrap().front().unwrap().non_null();
+        tree.push_back( Tree{ root: grandchild, mark: PhantomData });
+    }
+
+    #[cfg( all( debug_assertions, feature = "check_alias" ))]
+    #[test] #[should_panic( expected = "the tree to insert is already owned by the destination" )]
+    fn insert_aliased_sib() {
+        use crate::tr;
+
+        let mut forest = -tr(1) -( tr(2)/tr(3) );
+        let first = forest.front().unwrap().non_null();
+        forest.back_mut().unwrap().insert_next_sib( Tree{ root: first, mark: PhantomData });
+    }
+
+    #[cfg( all( debug_assertions, feature = "check_alias" ))]
+    #[test] fn push_distinct_trees() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1);
+        tree.push_front( tr(2)/tr(3) );
+        tree.front_mut().unwrap().push_back( tr(4) );
+        tree.back_mut().unwrap().insert_prev_sib( tr(5) );
+        assert_eq!( tree.to_string(), "0( 2( 3 4 ) 5 1 )" );
+    }
+
+    #[test] fn iter_mut_bfs() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(0)/tr(0) );
+        tree.iter_mut_bfs().for_each( |(level, data)| *data = level );
+        assert_eq!( tree, tr(0) /( tr(1)/tr(2) ));
+
+        let mut tree = tr('a') /( tr('b')/tr('d')/( tr('e')/tr('g') ) ) /( tr('c')/tr('f') );
+        let mut iter = tree.iter_mut_bfs();
+        assert_eq!( iter.len(), 7 );
+        iter.next();
+        assert_eq!( iter.len(), 6 );
+        let visited = iter.map( |(level, data)| { *data = data.to_ascii_uppercase(); (level, *data) }).collect::<Vec<_>>();
+        assert_eq!( visited, vec![ (1,'B'), (1,'C'), (2,'D'), (2,'E'), (2,'F'), (3,'G') ]);
+        assert_eq!( tree.to_string(), "a( B( D E( G ) ) C( F ) )" );
+    }
+
+    #[test] fn max_min_by_key() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(5)/tr(3) ) /tr(2);
+        assert_eq!( tree.max_by_key( |&x| x ).map( |node| *node.data() ), Some(5) );
+        assert_eq!( tree.min_by_key( |&x| x ).map( |node| *node.data() ), Some(1) );
+
+        let tree = tr((0,'a')) /( tr((2,'b'))/tr((1,'c'))/tr((2,'d')) ) /tr((0,'e'));
+        assert_eq!( tree.max_by_key( |&(k,_)| k ).map( |node| node.data().1 ), Some('b') );
+        assert_eq!( tree.min_by_key( |&(k,_)| k ).map( |node| node.data().1 ), Some('a') );
+        assert_eq!( tree.max_by_key( |_| 0 ), Some( tree.root() ));
+        assert_eq!( tr(7).min_by_key( |&x| -x ).map( |node| *node.data() ), Some(7) );
+    }
+
+    #[test] fn iter_mut_split_at() {
+        use crate::tr;
+
+        let mut tree = Tree::with_children( 0, (1..=10).map( tr ));
+        for mid in 0..=10 {
+            let (left, right) = tree.iter_mut().split_at( mid );
+            assert_eq!( (left.len(), right.len()), (mid, 10-mid) );
+            let sum = left.chain( right ).map( |mut node| { *node.data_mut() += 1; *node.data() }).sum::<i32>();
+            assert_eq!( sum, (2..=11).sum::<i32>() + 10*mid as i32 );
+        }
+        assert_eq!( tree.iter().map( |node| *node.data() ).collect::<Vec<_>>(), (1..=10).map( |x| x+11 ).collect::<Vec<_>>() );
+
+        let mut iter = tree.iter_mut();
+        iter.next();
+        let (left, mut right) = iter.split_at( 4 );
+        assert_eq!( left.map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 13, 14, 15, 16 ]);
+        assert_eq!( right.next().map( |node| *node.data() ), Some(17) );
+        assert_eq!( right.len(), 4 );
+    }
+
+    #[cfg( not( feature = "no_std" ))]
+    #[test] fn iter_mut_split_at_across_threads() {
+        use crate::tr;
+        use std::{pin::Pin, thread};
+
+        let mut tree = Tree::with_children( 0, (1..=100).map( tr ));
+        let (left, right) = tree.iter_mut().split_at( 40 );
+        let left = left.map( |node| Pin::into_inner( node ).data_mut() ).collect::<Vec<_>>();
+        let right = right.map( |node| Pin::into_inner( node ).data_mut() ).collect::<Vec<_>>();
+        let sums = thread::scope( |scope| {
+            let left = scope.spawn( || left.into_iter().map( |data| { *data += 1000; *data }).sum::<i32>() );
+            let right = scope.spawn( || right.into_iter().map( |data| { *data += 1000; *data }).sum::<i32>() );
+            (left.join().unwrap(), right.join().unwrap())
+        });
+        assert_eq!( sums.0 + sums.1, (1001..=1100).sum::<i32>() );
+        assert_eq!( tree.iter().map( |node| *node.data() ).collect::<Vec<_>>(), (1001..=1100).collect::<Vec<_>>() );
+    }
+
+    #[test] fn iter_last_and_rposition() {
+        use crate::tr;
+
+        let tree = tr(0) /tr(1)/tr(2)/tr(3)/( tr(4)/tr(5) );
+        assert_eq!( tree.iter().last(), Some( tree.back().unwrap() ));
+        assert_eq!( tree.iter().last().map( |node| *node.data() ), Some(4) );
+        assert_eq!( tr(0).iter().last(), None );
+
+        let mut iter = tree.iter();
+        assert_eq!( iter.next_back().map( |node| *node.data() ), Some(4) );
+        assert_eq!( iter.next().map( |node| *node.data() ), Some(1) );
+        assert_eq!( iter.len(), 2 );
+        assert_eq!( iter.rev().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 3, 2 ]);
+
+        assert_eq!( tree.iter().rposition( |node| node.data() % 2 == 1 ), Some(2) );
+        assert_eq!( tree.iter().rposition( |node| *node.data() == 5 ), None );
+
+        let first = tree.front().unwrap();
+        assert_eq!( first.following_siblings().last().map( |node| *node.data() ), Some(4) );
+        assert_eq!( first.following_siblings().rev().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 4, 3, 2 ]);
+        assert_eq!( tree.back().unwrap().following_siblings().last(), None );
+    }
+
+    #[test] #[should_panic] fn iter_mut_split_at_out_of_bounds() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1);
+        let _ = tree.iter_mut().split_at( 2 );
+    }
+
+    #[test] fn data_iter() {
+        use crate::tr;
+
+        let tree = tr(0) /( tr(1)/( tr(2)/tr(3) )/tr(4) ) /tr(5) /( tr(6)/tr(7) );
+        assert_eq!( tree.data_iter().collect::<Vec<_>>(), tree.clone().into_vec() );
+        assert_eq!( tree.data_iter().collect::<Vec<_>>(), tree.to_vec() );
+        assert_eq!( tr('x').data_iter().collect::<Vec<_>>(), vec![ 'x' ]);
+    }
+
+    #[test] fn merge() {
+        use crate::tr;
+
+        let mut tree = tr('a') /( tr('b')/tr('c') );
+        assert_eq!( tree.merge( tr('a') /( tr('b')/tr('d') )), Ok(()) );
+        assert_eq!( tree.to_string(), "a( b( c d ) )" );
+
+        let mut trie = tr('^') /( tr('t')/( tr('o')/tr('p') ) );
+        for word in &[ "top", "tot", "tip", "ape", "top" ] {
+            let path = word.chars().rev().fold( None, |child: Option<Tree<char>>, ch| Some( match child {
+                Some( child ) => tr( ch ) /child,
+                None => tr( ch ),
+            })).unwrap();
+            assert!( trie.merge( tr('^') /path ).is_ok() );
+        }
+        assert_eq!( trie.to_string(), "^( t( o( p t ) i( p ) ) a( p( e ) ) )" );
+        assert_eq!( trie.node_count(), 10 );
+        assert!( trie.validate().is_ok() );
+
+        let mut tree = tr(1) /tr(2) /tr(2);
+        assert_eq!( tree.merge( tr(1) /( tr(2)/tr(3) ) /tr(4) ), Ok(()) );
+        assert_eq!( tree.to_string(), "1( 2( 3 ) 2 4 )" );
+        assert_eq!( tree.merge( tr(0) /tr(5) ), Err( tr(0) /tr(5) ));
+        assert_eq!( tree.node_count(), 5 );
+    }
+
+    #[test] fn replace_data() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4);
+        assert_eq!( tree.replace_data( &[0,1], 5 ), Some(3) );
+        assert_eq!( tree.replace_data( &[], 6 ), Some(0) );
+        assert_eq!( tree.replace_data( &[1], 7 ), Some(4) );
+        assert_eq!( tree.to_string(), "6( 1( 2 5 ) 7 )" );
+        assert_eq!( tree.replace_data( &[0,2], 8 ), None );
+        assert_eq!( tree.replace_data( &[1,0], 8 ), None );
+        assert_eq!( tree.to_string(), "6( 1( 2 5 ) 7 )" );
+    }
+
+    #[test] fn lca() {
+        use crate::tr;
+
+        let tree = tr('r') /( tr('a')/tr('b')/tr('c') );
+        assert_eq!( tree.lca( &[0,0], &[0,1] ), Some( tree.front().unwrap() ));
+        assert_eq!( tree.lca( &[0,0], &[0,0] ), tree.front().unwrap().front() );
+        assert_eq!( tree.lca( &[], &[0,1] ), Some( tree.root() ));
+        assert_eq!( tree.lca( &[0,2], &[0,1] ), None );
+        assert_eq!( tree.lca( &[0,0], &[0,0,0] ), None );
+    }
+
+    #[test] fn swap_subtrees() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/( tr(3)/tr(4)/tr(5) )) /tr(6) /tr(7);
+        assert!( tree.swap( &[0,1], &[2] ));
+        assert_eq!( tree.to_string(), "0( 1( 2 7 ) 6 3( 4 5 ) )" );
+        assert_eq!( tree.node_count(), 8 );
+        assert_eq!( tree.front().unwrap().node_count(), 3 );
+        assert_eq!( tree.back().unwrap().parent(), Some( tree.root() ));
+
+        assert!( tree.swap( &[2], &[1] ));
+        assert_eq!( tree.to_string(), "0( 1( 2 7 ) 3( 4 5 ) 6 )" );
+        assert!( tree.swap( &[0,0], &[0,1] ));
+        assert_eq!( tree.to_string(), "0( 1( 7 2 ) 3( 4 5 ) 6 )" );
+        assert!( tree.swap( &[0], &[2] ));
+        assert_eq!( tree.to_string(), "0( 6 3( 4 5 ) 1( 7 2 ) )" );
+        assert_eq!( tree.iter().map( |node| node.node_count() ).collect::<Vec<_>>(), vec![ 1, 3, 3 ]);
+
+        assert!( tree.swap( &[], &[] ));
+        assert!( !tree.swap( &[], &[0] ));
+        assert!( !tree.swap( &[1,0], &[1] ));
+
+        let mut tree = Tree::<i32>::from_tuple(( 0, (1,2), (3,4) ));
+        assert!( tree.swap( &[0,0], &[1,0] ));
+        assert_eq!( tree.to_string(), "0( 1( 4 ) 3( 2 ) )" );
+    }
+
+    #[test] fn prune_if_skips_pruned_descendants() {
+        use crate::tr;
+
+        let mut tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+        assert_eq!( tree.prune_if( |&x| x == 2 ), 1 );
+        assert_eq!( tree.to_string(), "1( 4 )" );
+        assert_eq!( tree.node_count(), 2 );
+
+        let mut tested = Vec::new();
+        let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /( tr(4)/( tr(5)/tr(6) )/tr(7) ) /tr(8);
+        assert_eq!( tree.prune_if( |&x| { tested.push( x ); x % 2 == 1 }), 3 );
+        tested.sort();
+        assert_eq!( tested, vec![ 1, 4, 5, 7, 8 ]);
+        assert_eq!( tree.to_string(), "0( 4 8 )" );
+        assert_eq!( tree.node_count(), 3 );
+
+        let mut tree = Tree::<i32>::from_tuple(( 0, (1,2), 3, (4,5) ));
+        assert_eq!( tree.prune_if( |&x| x == 0 || x >= 4 ), 1 );
+        assert_eq!( tree.to_string(), "0( 1( 2 ) 3 )" );
+    }
+
+    #[test] fn try_map_stops_at_first_error() {
+        use crate::tr;
+        use crate::rust::Rc;
+
+        let counter = Rc::new(());
+        let tree = tr(1) /( tr(2)/tr(3)/tr(-4)/tr(5) ) /tr(6);
+        let mut visited = Vec::new();
+        let result = tree.try_map( |&x| {
+            visited.push( x );
+            if x < 0 { Err( x ) } else { Ok( counter.clone() )}
+        });
+        assert_eq!( result.err(), Some( -4 ));
+        assert_eq!( visited, vec![ 1, 2, 3, -4 ]);
+        assert_eq!( Rc::strong_count( &counter ), 1 );
+    }
+
+    #[test] fn eq_unordered() {
+        use crate::tr;
+
+        let a = tr(1) /tr(2)/tr(3);
+        let b = tr(1) /tr(3)/tr(2);
+        assert!( a.eq_unordered( &b ));
+        assert_ne!( a, b );
+
+        let a = tr(0) /( tr(1)/tr(2) ) /( tr(1)/tr(3)/tr(4) ) /tr(1);
+        assert!( a.eq_unordered( &( tr(0) /tr(1) /( tr(1)/tr(4)/tr(3) ) /( tr(1)/tr(2) ))));
+        assert!( !a.eq_unordered( &( tr(0) /( tr(1)/tr(3) ) /( tr(1)/tr(2)/tr(4) ) /tr(1) )));
+        assert!( !a.eq_unordered( &( tr(0) /( tr(1)/tr(2)/tr(3)/tr(4) ) /tr(1) /tr(1) )));
+        assert!( !( tr(0) /( tr(1)/tr(2) )).eq_unordered( &( tr(0) /tr(1) /tr(2) )));
+    }
+
+    #[test] fn eq_unordered_deep() {
+        use crate::tr;
+
+        let depth = 200_000;
+        let chain = |leaves: (i32, i32)| {
+            let mut chain = tr(0) /tr( leaves.0 )/tr( leaves.1 );
+            for data in 1..depth {
+                chain = tr( data ) / chain;
+            }
+            chain
+        };
+        assert!( chain(( 1, 2 )).eq_unordered( &chain(( 2, 1 ))));
+        assert!( !chain(( 1, 2 )).eq_unordered( &chain(( 1, 3 ))));
+    }
+
+    #[test] fn graft_forest_under_each_child() {
+        use crate::tr;
+
+        let mut tree = tr(0) /tr(1)/tr(2);
+        tree.iter_mut().for_each( |mut child| child.append( -tr(8)-tr(9) ));
+        assert_eq!( tree.to_string(), "0( 1( 8 9 ) 2( 8 9 ) )" );
+        assert_eq!( tree.node_count(), 7 );
+        tree.iter_mut().for_each( |mut child| child.prepend( -tr(7) ));
+        assert_eq!( tree.to_string(), "0( 1( 7 8 9 ) 2( 7 8 9 ) )" );
+        assert_eq!( tree.iter().map( |child| child.back().map( |node| *node.data() )).collect::<Vec<_>>(), vec![ Some(9), Some(9) ]);
+        assert_eq!( tree.node_count(), 9 );
+    }
+
+    #[test] fn descendants_in_pre_order() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/( tr(2)/tr(3) ) ) /tr(4) /( tr(5)/tr(6) );
+        let mut iter = tree.root().iter_descendants();
+        assert_eq!( iter.len(), 6 );
+        assert_eq!( iter.by_ref().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 1, 2, 3, 4, 5, 6 ]);
+        assert_eq!( iter.next(), None );
+        assert_eq!( tree.front().unwrap().iter_descendants().map( |node| *node.data() ).collect::<Vec<_>>(), vec![ 2, 3 ]);
+
+        tree.root_mut().iter_descendants_mut().for_each( |data| *data += 10 );
+        assert_eq!( tree.to_string(), "0( 11( 12( 13 ) ) 14 15( 16 ) )" );
+    }
+
+    #[test] fn reverse_all() {
+        use crate::tr;
+
+        let mut tree = tr(1) /( tr(2)/tr(4) ) /tr(3);
+        tree.reverse_all();
+        assert_eq!( tree.to_string(), "1( 3 2( 4 ) )" );
+        tree.reverse_all();
+        assert_eq!( tree, tr(1) /( tr(2)/tr(4) ) /tr(3) );
+
+        let mut tree = tr(0) /( tr(1) /tr(2) /( tr(3)/tr(4)/tr(5) )) /tr(6) /( tr(7)/tr(8)/tr(9) );
+        tree.reverse_all();
+        assert_eq!( tree.to_string(), "0( 7( 9 8 ) 6 1( 3( 5 4 ) 2 ) )" );
+        assert_eq!( tree.front().unwrap().back().unwrap().parent().map( |node| *node.data() ), Some(7) );
+        assert_eq!( tree.iter().map( |node| node.node_count() ).collect::<Vec<_>>(), vec![ 3, 1, 5 ]);
+        assert_eq!( tree.pop_back(), Some( tr(1) /( tr(3)/tr(5)/tr(4) ) /tr(2) ));
+        assert_eq!( tree.to_string(), "0( 7( 9 8 ) 6 )" );
+    }
+
+    #[test] fn degree_after_mutation() {
+        use crate::tr;
+
+        fn check( tree: &Tree<i32> ) {
+            for (_, node) in tree.iter_depth() {
+                let linked = node.front().map_or( 0, |front| 1 + front.following_siblings().count() );
+                assert_eq!( node.degree(), linked );
+            }
+        }
+
+        let mut tree = tr(0) /tr(1) /tr(2);
+        tree.push_front( tr(-1) );
+        tree.push_back( tr(3)/tr(30)/tr(31) );
+        check( &tree );
+        tree.root_mut().child_mut(1).unwrap().insert_next_sib( tr(10) );
+        tree.root_mut().front_mut().unwrap().insert_prev_sib( tr(-2) );
+        check( &tree );
+        assert_eq!( tree.degree(), 6 );
+        tree.pop_front();
+        tree.pop_back();
+        tree.root_mut().child_mut(1).unwrap().detach();
+        check( &tree );
+        assert_eq!( tree.to_string(), "0( -1 10 2 )" );
+        let forest = tree.root_mut().split_off( 1 );
+        tree.root_mut().front_mut().unwrap().append( forest );
+        check( &tree );
+        tree.root_mut().front_mut().unwrap().dissolve_child(0);
+        tree.root_mut().front_mut().unwrap().prepend( -tr(7)-tr(8) );
+        check( &tree );
+        assert_eq!( tree.to_string(), "0( -1( 7 8 2 ) )" );
+        assert_eq!( tree.front().unwrap().degree(), 3 );
+    }
+
+    #[test] fn leaf_data() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(2)/tr(4) ) /tr(3);
+        assert_eq!( tree.leaf_data(), vec![ &4, &3 ]);
+        assert_eq!( tree.into_leaf_data(), vec![ 4, 3 ]);
+
+        let tree = tr( "a".to_owned() ) /( tr( "b".to_owned() )/tr( "c".to_owned() )/( tr( "d".to_owned() )/tr( "e".to_owned() ))) /tr( "f".to_owned() );
+        assert_eq!( tree.leaf_data(), vec![ "c", "e", "f" ]);
+        assert_eq!( tree.into_leaf_data(), vec![ "c", "e", "f" ]);
+        assert_eq!( tr(0).into_leaf_data(), vec![ 0 ]);
+    }
+
+    #[test] fn paths() {
+        use crate::tr;
+
+        let data = |tree: &Tree<i32>| tree.paths()
+            .map( |path| path.iter().map( |node| *node.data() ).collect::<Vec<_>>() )
+            .collect::<Vec<_>>();
+        let tree = tr(0) /( tr(1)/( tr(2)/tr(3)/tr(4) )/tr(5) ) /tr(6) /( tr(7)/tr(8) );
+        assert_eq!( data( &tree ), vec![ vec![ 0, 1, 2, 3 ], vec![ 0, 1, 2, 4 ], vec![ 0, 1, 5 ], vec![ 0, 6 ], vec![ 0, 7, 8 ]]);
+        assert_eq!( data( &tr(9) ), vec![ vec![ 9 ]]);
+        assert_eq!( tree.paths().count(), tree.iter_postorder().filter( |node| node.has_no_child() ).count() );
+        assert!( tree.paths().all( |path| path[0] == tree.root() ));
+    }
+
+    #[test] fn update_postorder() {
+        use crate::tr;
+
+        let mut tree = tr(1) /( tr(2)/( tr(3)/tr(4) )/tr(5) ) /tr(6) /( tr(7)/tr(8) );
+        let mut visited = Vec::new();
+        tree.update_postorder( |mut node| {
+            visited.push( *node.data() );
+            let sum = node.iter().map( |child| *child.data() ).sum::<i32>();
+            *node.data_mut() += sum;
+        });
+        assert_eq!( visited, vec![ 4, 3, 5, 2, 6, 8, 7, 1 ]);
+        assert_eq!( tree.to_string(), "36( 14( 7( 4 ) 5 ) 6 15( 8 ) )" );
+
+        let mut leaf = tr(0);
+        leaf.update_postorder( |mut node| *node.data_mut() = 9 );
+        assert_eq!( leaf, tr(9) );
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /tr(4);
+        tree.update_postorder( |mut node| if *node.data() == 1 { drop( node.detach() ); });
+        assert_eq!( tree, tr(0)/tr(4) );
+        assert!( tree.validate().is_ok() );
+
+        let mut chain = tr(0);
+        for depth in 1..100_000 {
+            chain = tr(depth) /chain;
+        }
+        let mut count = 0;
+        chain.update_postorder( |_| count += 1 );
+        assert_eq!( count, 100_000 );
+    }
+
+    #[test] fn is_height_balanced() {
+        use crate::tr;
+
+        assert!( tr(0).is_height_balanced() );
+        assert!( ( tr(0) /tr(1) ).is_height_balanced() );
+        assert!( ( tr(0) /( tr(1)/tr(2)/tr(3) ) /( tr(4)/tr(5) ) /tr(6) ).is_height_balanced() );
+        assert!( !( tr(0) /( tr(1)/( tr(2)/tr(3) ) ) /tr(4) ).is_height_balanced() );
+        assert!( !( tr(0) /( tr(1) /( tr(2)/( tr(3)/tr(4) )) /tr(5) ) /( tr(6)/tr(7) ) ).is_height_balanced() );
+        assert!( ( tr(0) /( tr(1)/( tr(2)/tr(3) ) ) ).is_height_balanced() );
+    }
+
+    #[test] fn iter_depth() {
+        use crate::tr;
+
+        let tree = tr(0) /( tr(1)/( tr(2)/( tr(3)/tr(4) ) ) ) /tr(5) /( tr(6)/tr(7) );
+        let mut iter = tree.iter_depth();
+        assert_eq!( iter.len(), 8 );
+        iter.next();
+        assert_eq!( iter.size_hint(), (7, Some(7)) );
+        let depths = iter.by_ref().map( |(depth, node)| (depth, *node.data()) ).collect::<Vec<_>>();
+        assert_eq!( depths, vec![ (1,1), (2,2), (3,3), (4,4), (1,5), (1,6), (2,7) ]);
+        assert_eq!( iter.next(), None );
+
+        let pairs = tree.iter_depth().map( |(depth, node)| (depth, *node.data()) ).collect::<Vec<_>>();
+        assert_eq!( Tree::from_depths( pairs ), Some( tree ));
+        assert_eq!( tr(0).iter_depth().collect::<Vec<_>>(), vec![ (0, tr(0).root()) ]);
+    }
+
+    #[test] fn from_depths_in_arena() {
+        use crate::tr;
+
+        let tree = tr(0) /( tr(1)/( tr(2)/tr(3) )/tr(4) ) /tr(5) /( tr(6)/tr(7) );
+        let pairs = tree.iter_depth().map( |(depth, node)| (depth, *node.data()) ).collect::<Vec<_>>();
+        let mut piled = Tree::from_depths_in_arena( pairs.clone() ).unwrap();
+        assert_eq!( piled, tree );
+        assert_eq!( piled, Tree::from_depths( pairs ).unwrap() );
+        assert!( piled.validate().is_ok() );
+
+        let detached = piled.root_mut().front_mut().unwrap().detach();
+        piled.push_back( tr(8) );
+        drop( piled );
+        assert_eq!( detached.to_string(), "1( 2( 3 ) 4 )" );
+        assert!( detached.validate().is_ok() );
+
+        let depth = 200_000;
+        let chain = Tree::from_depths_in_arena( (0..depth).map( |depth| (depth, depth) )).unwrap();
+        assert_eq!( chain.node_count(), depth );
+        assert_eq!( chain.iter_depth().last().map( |(depth, node)| (depth, *node.data()) ), Some(( depth-1, depth-1 )));
+    }
+
+    #[test] fn from_depths_round_trip() {
+        fn depths( node: &Node<i32>, depth: usize, pairs: &mut Vec<(usize,i32)> ) {
+            pairs.push(( depth, *node.data() ));
+            node.iter().for_each( |child| depths( child, depth+1, pairs ));
+        }
+
+        use crate::tr;
+        let tree = tr(0) /( tr(1)/tr(2)/( tr(3)/tr(4) )) /tr(5) /( tr(6)/tr(7) );
+        let mut pairs = Vec::new();
+        depths( tree.root(), 0, &mut pairs );
+        assert_eq!( Tree::from_depths( pairs ), Some( tree ));
+    }
+
+    #[test] fn parent_while_inserting_sibs() {
+        use crate::tr;
+
+        let mut tree = tr(9) /tr(1)/tr(2);
+        tree.iter_mut().for_each( |mut sub| {
+            let data = *sub.parent().unwrap().data() + *sub.data();
+            sub.insert_next_sib( tr( data ));
+        });
+        assert_eq!( tree, tr(9) /tr(1)/tr(10)/tr(2)/tr(11) );
+
+        let mut forest = -tr(1)-tr(2);
+        forest.iter_mut().for_each( |sub| assert!( sub.parent().is_none() ));
+    }
+
+    #[test] fn into_forest() {
+        use crate::{Forest, Tree, tr};
+
+        let forest = ( tr(0) /tr(1)/( tr(2)/tr(3) ) ).into_forest();
+        assert_eq!( forest, -tr(1)-( tr(2)/tr(3) ));
+        assert!( forest.validate().is_ok() );
+        let tree = Tree::from_forest( 0, forest );
+        assert_eq!( tree, tr(0) /tr(1)/( tr(2)/tr(3) ));
+        assert!( tree.validate().is_ok() );
+        assert_eq!( Tree::from_forest( 0, Forest::new() ), tr(0) );
+        assert!( tr(0).into_forest().has_no_child() );
+    }
 }
 
 #[cfg( miri )]
@@ -330,6 +1885,76 @@ mod miri_tests {
         assert_eq!( tree, Tree::new(0) );
     }
 
+    #[test] fn alternate_display() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+        assert_eq!( format!( "{}", tree ), "1( 2( 3 ) 4 )" );
+        assert_eq!( format!( "{:#}", tree ), "1\n  2\n    3\n  4" );
+        assert_eq!( format!( "{:-<#1}", tree ), "1\n-2\n--3\n-4" );
+    }
+
+    #[test] fn try_map() {
+        use crate::tr;
+
+        let tree = tr("1") /( tr("2")/tr("3") );
+        assert_eq!( tree.try_map( |s| s.parse::<i32>() ), Ok( tr(1) /( tr(2)/tr(3) )));
+
+        let tree = tr("1") /tr("x");
+        assert!( tree.try_map( |s| s.parse::<i32>() ).is_err() );
+    }
+
+    #[test] fn from_depths() {
+        use crate::{Tree, tr};
+
+        let tree = Tree::from_depths( vec![ (0,'a'), (1,'b'), (2,'c'), (1,'d') ]);
+        assert_eq!( tree, Some( tr('a') /( tr('b')/tr('c') ) /tr('d') ));
+
+        assert_eq!( Tree::from_depths( vec![ (0,'a'), (2,'b') ]), None );
+        assert_eq!( Tree::from_depths( vec![ (1,'a') ]), None );
+        assert_eq!( Tree::<char>::from_depths( vec![] ), None );
+    }
+
+    #[test] fn into_vec() {
+        use crate::{Tree, tr};
+
+        let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+        assert_eq!( tree.into_vec(), vec![ 1, 2, 3, 4 ]);
+
+        let tree = Tree::<i32>::from_tuple(( 1, (2,3), 4 ));
+        assert_eq!( tree.into_vec(), vec![ 1, 2, 3, 4 ]);
+    }
+
+    #[test] fn to_vec() {
+        use crate::tr;
+
+        let tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+        assert_eq!( tree.to_vec(), vec![ 1, 2, 3, 4 ]);
+        assert_eq!( tree.to_string(), "1( 2( 3 ) 4 )" );
+    }
+
+    #[test] fn swap() {
+        use crate::tr;
+
+        let mut tree = tr(0) /( tr(1)/tr(2)/tr(3) ) /( tr(4)/tr(5) );
+        assert!( tree.swap( &[0,1], &[1,0] ));
+        assert_eq!( tree.to_string(), "0( 1( 2 5 ) 4( 3 ) )" );
+        assert!( tree.swap( &[0], &[1] ));
+        assert_eq!( tree.to_string(), "0( 4( 3 ) 1( 2 5 ) )" );
+
+        assert!( !tree.swap( &[0], &[0,0] ));
+        assert!( !tree.swap( &[0], &[2] ));
+        assert_eq!( tree.to_string(), "0( 4( 3 ) 1( 2 5 ) )" );
+    }
+
+    #[test] fn prune_if() {
+        use crate::tr;
+
+        let mut tree = tr(1) /( tr(2)/tr(3) ) /tr(4);
+        assert_eq!( tree.prune_if( |&x| x == 2 ), 1 );
+        assert_eq!( tree.to_string(), "1( 4 )" );
+    }
+
     #[test] fn pop_front() {
         use crate::Tree;
 
@@ -24,23 +24,40 @@ impl<'a, T:'a> Visit<'a,T> {
 }
 
 // Mapping to Option<Visit>
+#[derive( Copy, Clone, Debug, Eq, PartialEq )]
 enum VisitType{ None, Begin, End, Leaf }
 
 /// Cursor on `Node` and its siblings.
 struct Nodes<T> {
     node     : Option<NonNull<Node<T>>>,
     sentinel : Option<NonNull<Node<T>>>,
+    index    : usize, // of `node` among its siblings.
 }
 
 impl<T> Nodes<T> {
     /// Only the given node will be visited.
-    fn this( node: Option<NonNull<Node<T>>> ) -> Self { Nodes{ node, sentinel: node.map( |node| unsafe{ node.as_ref().next }).unwrap_or( None ) }}
+    fn this( node: Option<NonNull<Node<T>>> ) -> Self {
+        let mut index = 0;
+        let mut prev = node.and_then( |node| unsafe{ node.as_ref().prev });
+        while let Some( node ) = prev {
+            index += 1;
+            prev = unsafe{ node.as_ref().prev };
+        }
+        Nodes{ node, sentinel: node.map( |node| unsafe{ node.as_ref().next }).unwrap_or( None ), index }
+    }
 
     /// The given node and all its siblings will be visited.
-    fn sibs( node: Option<NonNull<Node<T>>> ) -> Self { Nodes{ node, sentinel: None }}
+    fn sibs( node: Option<NonNull<Node<T>>> ) -> Self { Nodes{ node, sentinel: None, index: 0 }}
+}
+
+impl<T> Copy for Nodes<T> {}
+
+impl<T> Clone for Nodes<T> {
+    fn clone( &self ) -> Self { *self }
 }
 
 // Control of the `Walk`'s stack.
+#[derive( Copy, Clone, Debug, Eq, PartialEq )]
 enum Direction {
     Up,    // Current node and all its siblings and all their descendents have been visited, so go back to their parent.
     Down,  // Try to visit the first child of the current node.
@@ -53,10 +70,12 @@ struct Walk<T> {
     direction  : Direction,
     visit_type : VisitType,                // maps to Option<Visit>, needed by get().
     origin     : Option<NonNull<Node<T>>>, // for rewind.
+    peeked     : Option<(Vec<Nodes<T>>, Direction, VisitType)>, // state after the next forward(), cached by peek().
 }
 
 impl<T> Walk<T> {
     fn reset( &mut self ) {
+        self.peeked = None;
         self.path.clear();
         self.direction = Direction::Down;
         self.visit_type = VisitType::None;
@@ -92,10 +111,10 @@ impl<T> Walk<T> {
     }
 
     fn revisit( &mut self ) {
+        self.peeked = None;
         if self.origin.is_some() {
-            match self.visit_type {
-                VisitType::None => self.path.push( Nodes::sibs( self.origin )),
-                _ => (),
+            if self.visit_type == VisitType::None {
+                self.path.push( Nodes::sibs( self.origin ));
             }
             self.direction = Direction::Down;
             self.init_visit();
@@ -104,8 +123,12 @@ impl<T> Walk<T> {
 
     /// Returns the current node in the traversal, or `None` if the traversal is completed.
     fn get( &self ) -> Option<Visit<T>> {
-        if let Some( nodes ) = self.path.last() {
-            unsafe { match self.visit_type {
+        Walk::visit( &self.path, self.visit_type )
+    }
+
+    fn visit( path: &[Nodes<T>], visit_type: VisitType ) -> Option<Visit<'_,T>> {
+        if let Some( nodes ) = path.last() {
+            unsafe { match visit_type {
                 VisitType::Begin => Some( Visit::Begin( &*nodes.node.unwrap().as_ptr() )),
                 VisitType::End   => Some( Visit::End  ( &*nodes.node.unwrap().as_ptr() )),
                 VisitType::Leaf  => Some( Visit::Leaf ( &*nodes.node.unwrap().as_ptr() )),
@@ -118,6 +141,17 @@ impl<T> Walk<T> {
 
     /// Advances the cursor in the traversal.
     fn forward( &mut self ) {
+        match self.peeked.take() {
+            Some( (path, direction, visit_type) ) => {
+                self.path = path;
+                self.direction = direction;
+                self.visit_type = visit_type;
+            },
+            None => self.advance(),
+        }
+    }
+
+    fn advance( &mut self ) {
         loop {
             match self.direction {
                 Direction::Up => {
@@ -148,6 +182,7 @@ impl<T> Walk<T> {
                 Direction::Right => {
                     if let Some( nodes ) = self.path.last_mut() {
                         nodes.node = unsafe{ nodes.node.unwrap().as_ref().next };
+                        nodes.index += 1;
                         if nodes.node == nodes.sentinel {
                             self.direction = Direction::Up;
                             continue;
@@ -174,8 +209,49 @@ impl<T> Walk<T> {
         self.get()
     }
 
+    /// Returns the node which `next()` would visit, without advancing the cursor.
+    fn peek( &mut self ) -> Option<Visit<'_,T>> {
+        if self.peeked.is_none() {
+            let (path, direction, visit_type) = (self.path.clone(), self.direction, self.visit_type);
+            self.advance();
+            self.peeked = Some((
+                mem::replace( &mut self.path,       path       ),
+                mem::replace( &mut self.direction,  direction  ),
+                mem::replace( &mut self.visit_type, visit_type ),
+            ));
+        }
+        self.peeked.as_ref().and_then( |(path, _, visit_type)| Walk::visit( path, *visit_type ))
+    }
+
+    fn position( &self ) -> WalkPos {
+        WalkPos {
+            indices    : self.path.iter().map( |nodes| nodes.index ).collect(),
+            direction  : self.direction,
+            visit_type : self.visit_type,
+        }
+    }
+
+    // Locates the nodes on the path by their indices among siblings, starting from `origin`.
+    fn resume( &mut self, pos: &WalkPos ) {
+        self.peeked = None;
+        self.path.clear();
+        let mut head = self.origin;
+        for &index in &pos.indices {
+            let mut nodes = Nodes::sibs( head );
+            for _ in 0..index {
+                nodes.node = nodes.node.and_then( |node| unsafe{ node.as_ref().next });
+            }
+            nodes.index = index;
+            head = nodes.node.and_then( |node| unsafe{ node.as_ref().head });
+            self.path.push( nodes );
+        }
+        self.direction = pos.direction;
+        self.visit_type = pos.visit_type;
+    }
+
     /// Sets the cursor to the current node's parent and returns it, or `None` if it has no parent.
     fn to_parent( &mut self ) -> Option<Visit<T>> {
+        self.peeked = None;
         if self.path.last().is_some() {
             self.path.pop();
             if self.path.last().is_some() {
@@ -201,9 +277,11 @@ impl<T> Walk<T> {
     /// Sets the cursor to the current node's next `n`-th sibling and returns it, or `None` if such sibling does not exist.
     /// Returns the current node if n == 0.
     fn to_sib( &mut self, n: usize ) -> Option<Visit<T>> {
+        self.peeked = None;
         if let Some( nodes ) = self.path.last_mut() {
             for _ in 0..n {
                 nodes.node = unsafe{ nodes.node.unwrap().as_ref().next };
+                nodes.index += 1;
                 if nodes.node == nodes.sentinel {
                     self.direction = Direction::Up;
                     return None;
@@ -224,6 +302,7 @@ impl<T> Walk<T> {
     /// Sets the cursor to the current node's `n`-th child and returns it, or `None` if it has no child.
     /// Notice that `n == 0` indicating the first child.
     fn to_child( &mut self, n: usize ) -> Option<Visit<T>> {
+        self.peeked = None;
         let new_nodes;
         if let Some( nodes ) = self.path.last_mut() {
             unsafe {
@@ -245,9 +324,55 @@ impl<T> Walk<T> {
     }
 }
 
+/// An iterator over the visits of a `TreeWalk` or `ForestWalk` from its current position,
+/// paired with the visited node's index among its siblings.
+///
+/// This `struct` is created by [`TreeWalk::walk_indexed`] and [`ForestWalk::walk_indexed`].
+/// See their documents for more.
+///
+/// [`TreeWalk::walk_indexed`]: struct.TreeWalk.html#method.walk_indexed
+/// [`ForestWalk::walk_indexed`]: struct.ForestWalk.html#method.walk_indexed
+pub struct WalkIndexed<'a, T:'a> {
+    walk : &'a mut Walk<T>,
+}
+
+impl<'a, T:'a> Iterator for WalkIndexed<'a,T> {
+    type Item = (usize, Visit<'a,T>);
+
+    fn next( &mut self ) -> Option<Self::Item> {
+        let nodes = *self.walk.path.last()?;
+        let node = unsafe{ &*nodes.node?.as_ptr() };
+        let visit = match self.walk.visit_type {
+            VisitType::Begin => Visit::Begin( node ),
+            VisitType::End   => Visit::End  ( node ),
+            VisitType::Leaf  => Visit::Leaf ( node ),
+            VisitType::None  => return None,
+        };
+        self.walk.forward();
+        Some(( nodes.index, visit ))
+    }
+}
+
+/// A saved position of a `TreeWalk` or `ForestWalk`, to resume the traversal later.
+///
+/// The position records the indices of the current node and its ancestors among their siblings,
+/// rather than pointers to them, so it is cheap to clone and does not borrow the walk.
+///
+/// This `struct` is created by [`TreeWalk::position`] and [`ForestWalk::position`].
+/// See their documents for more.
+///
+/// [`TreeWalk::position`]: struct.TreeWalk.html#method.position
+/// [`ForestWalk::position`]: struct.ForestWalk.html#method.position
+#[derive( Clone, Debug, Eq, PartialEq )]
+pub struct WalkPos {
+    indices    : Vec<usize>,
+    direction  : Direction,
+    visit_type : VisitType,
+}
+
 impl<T> Default for Walk<T> {
     fn default() -> Self {
-        Walk{ path: Vec::default(), direction: Direction::Down, visit_type: VisitType::None, origin: None }
+        Walk{ path: Vec::default(), direction: Direction::Down, visit_type: VisitType::None, origin: None, peeked: None }
     }
 }
 
@@ -324,6 +449,22 @@ impl<T> TreeWalk<T> {
     /// ```
     pub fn next( &mut self ) -> Option<Visit<T>> { self.walk.next() }
 
+    /// Returns the node which `next()` would visit, without advancing the cursor.
+    /// The result is cached, so the following `next()` does not traverse again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{TreeWalk, tr, walk::Visit};
+    /// let tree = tr(0) /( tr(1)/tr(2) );
+    /// let mut walk = TreeWalk::from( tree );
+    /// assert_eq!( walk.peek(), Some( Visit::Begin( (tr(1)/tr(2)).root() )));
+    /// assert_eq!( walk.get(), Some( Visit::Begin( ( tr(0) /( tr(1)/tr(2) ) ).root() )));
+    /// assert_eq!( walk.next(), Some( Visit::Begin( (tr(1)/tr(2)).root() )));
+    /// assert_eq!( walk.peek(), Some( Visit::Leaf( tr(2).root() )));
+    /// ```
+    pub fn peek( &mut self ) -> Option<Visit<'_,T>> { self.walk.peek() }
+
     /// Set the cursor to the current node's parent and returns it, or `None` if it has no parent.
     ///
     /// # Examples
@@ -430,6 +571,49 @@ impl<T> TreeWalk<T> {
     /// }
     /// ```
     pub fn revisit( &mut self ) { self.walk.revisit(); }
+
+    /// Provides an iterator over the visits from the current node to the end of the traversal,
+    /// each paired with the index of the visited node among its siblings. The root's index is 0.
+    ///
+    /// Unlike `next()`, the current node is the first to be yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{TreeWalk, tr, walk::Visit};
+    /// let mut walk = TreeWalk::from( tr(0) /( tr(1)/tr(2) ) /tr(3) );
+    /// let indices = walk.walk_indexed()
+    ///     .map( |(index, visit)| match visit {
+    ///         Visit::Begin( node ) => format!( "{}<{}", index, node.data() ),
+    ///         Visit::End  ( node ) => format!( "{}>{}", index, node.data() ),
+    ///         Visit::Leaf ( node ) => format!( "{}:{}", index, node.data() ),
+    ///     })
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!( indices, vec![ "0<0", "0<1", "0:2", "0>1", "1:3", "0>0" ]);
+    /// assert_eq!( walk.get(), None );
+    /// ```
+    pub fn walk_indexed( &mut self ) -> WalkIndexed<'_,T> { WalkIndexed{ walk: &mut self.walk }}
+
+    /// Saves the current position in the traversal, to be restored by [`resume`](#method.resume).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{TreeWalk, tr};
+    /// let mut walk = TreeWalk::from( tr(0) /( tr(1)/tr(2) ) /tr(3) );
+    /// walk.forward();
+    /// let pos = walk.position();
+    /// let rest = walk.walk_indexed().map( |(_, visit)| *visit.node().data() ).collect::<Vec<_>>();
+    /// walk.resume( &pos );
+    /// assert_eq!( walk.walk_indexed().map( |(_, visit)| *visit.node().data() ).collect::<Vec<_>>(), rest );
+    /// ```
+    pub fn position( &self ) -> WalkPos { self.walk.position() }
+
+    /// Restores a position saved by [`position`](#method.position), so that the traversal continues from there.
+    ///
+    /// The position should have been saved from this walk.
+    /// A position of another walk leads to an unspecified traversal, possibly panicking.
+    pub fn resume( &mut self, pos: &WalkPos ) { self.walk.resume( pos ); }
 }
 
 impl<T> From<Tree<T>> for TreeWalk<T> {
@@ -518,6 +702,22 @@ impl<T> ForestWalk<T> {
     /// ```
     pub fn next( &mut self ) -> Option<Visit<T>> { self.walk.next() }
 
+    /// Returns the node which `next()` would visit, without advancing the cursor.
+    /// The result is cached, so the following `next()` does not traverse again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{ForestWalk, tr, walk::Visit};
+    /// let forest = -tr(1)-tr(2);
+    /// let mut walk = ForestWalk::from( forest );
+    /// assert_eq!( walk.peek(), Some( Visit::Leaf( tr(2).root() )));
+    /// assert_eq!( walk.get(), Some( Visit::Leaf( tr(1).root() )));
+    /// assert_eq!( walk.next(), Some( Visit::Leaf( tr(2).root() )));
+    /// assert_eq!( walk.peek(), None );
+    /// ```
+    pub fn peek( &mut self ) -> Option<Visit<'_,T>> { self.walk.peek() }
+
     /// Sets the cursor to the current node's parent and returns it, or `None` if it has no parent.
     ///
     /// # Examples
@@ -616,6 +816,42 @@ impl<T> ForestWalk<T> {
     /// }
     /// ```
     pub fn revisit( &mut self ) { self.walk.revisit(); }
+
+    /// Provides an iterator over the visits from the current node to the end of the traversal,
+    /// each paired with the index of the visited node among its siblings, including the top-level ones.
+    ///
+    /// Unlike `next()`, the current node is the first to be yielded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{ForestWalk, tr};
+    /// let mut walk = ForestWalk::from( -tr(1)-( tr(2)/tr(3)/tr(4) ) );
+    /// let indices = walk.walk_indexed().map( |(index, visit)| (index, *visit.node().data()) ).collect::<Vec<_>>();
+    /// assert_eq!( indices, vec![ (0,1), (1,2), (0,3), (1,4), (1,2) ]);
+    /// ```
+    pub fn walk_indexed( &mut self ) -> WalkIndexed<'_,T> { WalkIndexed{ walk: &mut self.walk }}
+
+    /// Saves the current position in the traversal, to be restored by [`resume`](#method.resume).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{ForestWalk, tr};
+    /// let mut walk = ForestWalk::from( -( tr(1)/tr(2) ) -tr(3) );
+    /// walk.forward();
+    /// let pos = walk.position();
+    /// let rest = walk.walk_indexed().map( |(_, visit)| *visit.node().data() ).collect::<Vec<_>>();
+    /// walk.resume( &pos );
+    /// assert_eq!( walk.walk_indexed().map( |(_, visit)| *visit.node().data() ).collect::<Vec<_>>(), rest );
+    /// ```
+    pub fn position( &self ) -> WalkPos { self.walk.position() }
+
+    /// Restores a position saved by [`position`](#method.position), so that the traversal continues from there.
+    ///
+    /// The position should have been saved from this walk.
+    /// A position of another walk leads to an unspecified traversal, possibly panicking.
+    pub fn resume( &mut self, pos: &WalkPos ) { self.walk.resume( pos ); }
 }
 
 impl<T> From<Forest<T>> for ForestWalk<T> {
@@ -637,6 +873,96 @@ impl<T> From<ForestWalk<T>> for Forest<T> {
 unsafe impl<T:Send> Send for ForestWalk<T> {}
 unsafe impl<T:Sync> Sync for ForestWalk<T> {}
 
+#[cfg( test )]
+mod tests {
+    use crate::{TreeWalk, tr, walk::Visit};
+
+    #[test] fn peek_then_next_matches_next() {
+        let mut plain = TreeWalk::from( tr(1) /( tr(2)/tr(3) ));
+        let mut peeking = TreeWalk::from( tr(1) /( tr(2)/tr(3) ));
+        loop {
+            let peeked = peeking.peek().map( |visit| visit.node() as *const _ );
+            assert_eq!( peeking.peek().map( |visit| visit.node() as *const _ ), peeked );
+            let next = peeking.next();
+            assert_eq!( next.map( |visit| visit.node() as *const _ ), peeked );
+            assert_eq!( next, plain.next() );
+            let done = next.is_none();
+            assert_eq!( peeking.get_parent(), plain.get_parent() );
+            if done { break; }
+        }
+        assert_eq!( peeking.peek(), None );
+    }
+
+    #[test] fn walk_indexed() {
+        use crate::rust::*;
+
+        let mut walk = TreeWalk::from( tr('0') /tr('a') /( tr('b')/tr('x')/tr('y') ) /tr('c') );
+        walk.next();
+        let children = walk.walk_indexed()
+            .filter_map( |(index, visit)| match visit {
+                Visit::Begin( node ) | Visit::Leaf( node ) if node.parent().map( |parent| *parent.data() ) == Some('0') => Some(( index, *node.data() )),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!( children, vec![ (0,'a'), (1,'b'), (2,'c') ]);
+
+        let mut walk = TreeWalk::from( tr('0') /tr('a') /( tr('b')/tr('x')/tr('y') ) /tr('c') );
+        walk.to_child( 2 );
+        assert_eq!( walk.walk_indexed().next().map( |(index, visit)| (index, *visit.node().data()) ), Some(( 2, 'c' )));
+    }
+
+    #[test] fn resume_from_position() {
+        use crate::ForestWalk;
+        use crate::rust::*;
+
+        fn visits<'a, I:Iterator<Item=(usize, Visit<'a,i32>)>>( iter: I ) -> Vec<(usize, i32, u8)> {
+            iter.map( |(index, visit)| match visit {
+                Visit::Begin( node ) => (index, *node.data(), b'<'),
+                Visit::End  ( node ) => (index, *node.data(), b'>'),
+                Visit::Leaf ( node ) => (index, *node.data(), b'.'),
+            }).collect()
+        }
+
+        let tree = tr(0) /( tr(1)/tr(2)/( tr(3)/tr(4)/tr(5) ) ) /tr(6) /( tr(7)/tr(8) );
+        let steps = TreeWalk::from( tree.clone() ).walk_indexed().count();
+        assert_eq!( steps, 13 );
+        for n in 0..=steps {
+            let mut walk = TreeWalk::from( tree.clone() );
+            for _ in 0..n { walk.forward(); }
+            let pos = walk.position();
+            let expected = visits( walk.walk_indexed() );
+            assert_eq!( expected.len(), steps - n );
+
+            let mut other = TreeWalk::from( tree.clone() );
+            other.to_child( 2 );
+            other.peek();
+            other.resume( &pos );
+            assert_eq!( other.position(), pos );
+            assert_eq!( visits( other.walk_indexed() ), expected );
+        }
+
+        let forest = -( tr(1)/tr(2) ) -tr(3) -( tr(4)/tr(5) );
+        let mut walk = ForestWalk::from( forest );
+        walk.next();
+        walk.next();
+        let pos = walk.position();
+        assert_eq!( walk.next(), Some( Visit::Leaf( tr(3).root() )));
+        walk.next();
+        walk.resume( &pos );
+        assert_eq!( walk.get(), Some( Visit::End( ( tr(1)/tr(2) ).root() )));
+        assert_eq!( walk.next(), Some( Visit::Leaf( tr(3).root() )));
+    }
+
+    #[test] fn moving_cursor_discards_peeked() {
+        let mut walk = TreeWalk::from( tr(1) /( tr(2)/tr(3) ) /tr(4) );
+        walk.forward();
+        assert_eq!( walk.peek(), Some( Visit::Leaf( tr(3).root() )));
+        assert_eq!( walk.to_sib(1), Some( Visit::Leaf( tr(4).root() )));
+        assert_eq!( walk.peek(), Some( Visit::End( ( tr(1) /( tr(2)/tr(3) ) /tr(4) ).root() )));
+        assert_eq!( walk.next(), Some( Visit::End( ( tr(1) /( tr(2)/tr(3) ) /tr(4) ).root() )));
+    }
+}
+
 #[cfg( miri )]
 mod miri_tests {
     mod tree_walk {
@@ -0,0 +1,265 @@
+//! XML import/export with a fixed schema.
+//!
+//! Each node is encoded as an element `<node data="...">...</node>` enclosing its children's elements,
+//! with the data being the `Display` output of the node's data as an escaped attribute value.
+//! Leaf nodes are written as empty-element tags such as `<node data="..."/>`.
+
+use crate::rust::*;
+
+use super::{Error, Node, Tree};
+
+impl<T> Tree<T> {
+    /// Exports the tree as XML, each node encoded as `<node data="...">...</node>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::tr;
+    ///
+    /// let tree = tr(1) /( tr(2)/tr(3) );
+    /// assert_eq!( tree.to_xml(), r#"<node data="1"><node data="2"><node data="3"/></node></node>"# );
+    /// assert_eq!( tr( "<&>" ).to_xml(), r#"<node data="&lt;&amp;&gt;"/>"# );
+    /// ```
+    pub fn to_xml( &self ) -> String
+        where T: Display
+    {
+        let mut xml = String::new();
+        write_xml( self.root(), &mut xml );
+        xml
+    }
+}
+
+impl Tree<String> {
+    /// Parses a tree from XML in the schema produced by [`to_xml`](#method.to_xml).
+    ///
+    /// Whitespace between tags is ignored, and a node without children can be written either as
+    /// `<node data="..."/>` or as `<node data="..."></node>`.
+    /// The predefined entities and character references are recognized in attribute values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use trees::{Tree, tr};
+    ///
+    /// let tree = Tree::from_xml( r#"<node data="1"> <node data="2"></node> <node data="a&quot;b"/> </node>"# ).unwrap();
+    /// assert_eq!( tree.to_string(), r#"1( 2 a"b )"# );
+    ///
+    /// assert_eq!( Tree::from_xml( r#"<node data="1">"# ).unwrap_err().to_string(), "xml: missing </node>" );
+    /// ```
+    pub fn from_xml( s: &str ) -> Result<Tree<String>, Error> {
+        let mut parser = Parser{ chars: s.chars().peekable() };
+        let mut stack = Vec::<Tree<String>>::new();
+
+        let root = loop {
+            parser.skip_whitespace();
+            let tree = match parser.chars.next() {
+                Some( '<' ) if parser.chars.peek() == Some( &'/' ) => {
+                    parser.chars.next();
+                    parser.expect_str( "node" )?;
+                    parser.skip_whitespace();
+                    parser.expect( '>' )?;
+                    stack.pop().ok_or( "xml: unexpected </node>" )?
+                },
+                Some( '<' ) => {
+                    parser.expect_str( "node" )?;
+                    let tree = Tree::new( parser.parse_data()? );
+                    parser.skip_whitespace();
+                    match parser.chars.next() {
+                        Some( '/' ) => {
+                            parser.expect( '>' )?;
+                            tree
+                        },
+                        Some( '>' ) => {
+                            stack.push( tree );
+                            continue;
+                        },
+                        Some( ch ) => return Err( format!( "xml: expected '>' or '/>', found '{}'", ch ).into() ),
+                        None => return Err( "xml: expected '>' or '/>', found end of input".into() ),
+                    }
+                },
+                Some( ch ) => return Err( format!( "xml: unexpected '{}'", ch ).into() ),
+                None if stack.is_empty() => return Err( "xml: no root element".into() ),
+                None => return Err( "xml: missing </node>".into() ),
+            };
+            match stack.last_mut() {
+                Some( parent ) => parent.push_back( tree ),
+                None => break tree,
+            }
+        };
+
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok( root ),
+            Some( ch ) => Err( format!( "xml: unexpected '{}' after the root element", ch ).into() ),
+        }
+    }
+}
+
+fn write_xml<T:Display>( node: &Node<T>, xml: &mut String ) {
+    if write_open( node, xml ) {
+        return;
+    }
+    let mut iters = vec![ node.iter() ];
+    while let Some( iter ) = iters.last_mut() {
+        match iter.next() {
+            Some( child ) => if !write_open( child, xml ) {
+                iters.push( child.iter() );
+            },
+            None => {
+                iters.pop();
+                xml.push_str( "</node>" );
+            },
+        }
+    }
+}
+
+// Writes the node's start tag, or its empty-element tag returning `true` if it has no child.
+fn write_open<T:Display>( node: &Node<T>, xml: &mut String ) -> bool {
+    xml.push_str( r#"<node data=""# );
+    for ch in node.data().to_string().chars() {
+        match ch {
+            '&'  => xml.push_str( "&amp;" ),
+            '<'  => xml.push_str( "&lt;" ),
+            '>'  => xml.push_str( "&gt;" ),
+            '"'  => xml.push_str( "&quot;" ),
+            '\t' | '\n' | '\r' => xml.push_str( &format!( "&#{};", ch as u32 )),
+            _ => xml.push( ch ),
+        }
+    }
+    if node.has_no_child() {
+        xml.push_str( r#""/>"# );
+        true
+    } else {
+        xml.push_str( r#"">"# );
+        false
+    }
+}
+
+struct Parser<'a> {
+    chars : Peekable<Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace( &mut self ) {
+        while let Some( ch ) = self.chars.peek() {
+            if ch.is_whitespace() {
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn expect( &mut self, expected: char ) -> Result<(), Error> {
+        match self.chars.next() {
+            Some( ch ) if ch == expected => Ok(()),
+            Some( ch ) => Err( format!( "xml: expected '{}', found '{}'", expected, ch ).into() ),
+            None => Err( format!( "xml: expected '{}', found end of input", expected ).into() ),
+        }
+    }
+
+    fn expect_str( &mut self, expected: &str ) -> Result<(), Error> {
+        expected.chars().try_for_each( |ch| self.expect( ch ))
+    }
+
+    // Parses ` data="..."` following the element name.
+    fn parse_data( &mut self ) -> Result<String, Error> {
+        match self.chars.peek() {
+            Some( ch ) if ch.is_whitespace() => self.skip_whitespace(),
+            _ => return Err( r#"xml: missing attribute "data""#.into() ),
+        }
+        self.expect_str( "data" )?;
+        self.skip_whitespace();
+        self.expect( '=' )?;
+        self.skip_whitespace();
+        self.expect( '"' )?;
+
+        let mut data = String::new();
+        loop {
+            match self.chars.next() {
+                Some( '"' ) => return Ok( data ),
+                Some( '&' ) => data.push( self.parse_reference()? ),
+                Some( '<' ) => return Err( "xml: unescaped '<' in attribute value".into() ),
+                Some( ch ) => data.push( ch ),
+                None => return Err( "xml: unterminated attribute value".into() ),
+            }
+        }
+    }
+
+    // Parses the rest of an entity or character reference after '&'.
+    fn parse_reference( &mut self ) -> Result<char, Error> {
+        let mut name = String::new();
+        loop {
+            match self.chars.next() {
+                Some( ';' ) => break,
+                Some( ch ) if ch != '"' => name.push( ch ),
+                _ => return Err( "xml: unterminated reference".into() ),
+            }
+        }
+        let code = match name.as_str() {
+            "amp"  => return Ok( '&' ),
+            "lt"   => return Ok( '<' ),
+            "gt"   => return Ok( '>' ),
+            "quot" => return Ok( '"' ),
+            "apos" => return Ok( '\'' ),
+            _ if name.starts_with( "#x" ) => u32::from_str_radix( &name[2..], 16 ).ok(),
+            _ if name.starts_with( '#' ) => name[1..].parse::<u32>().ok(),
+            _ => None,
+        };
+        code.and_then( char::from_u32 )
+            .ok_or_else( || format!( "xml: unknown reference '&{};'", name ).into() )
+    }
+}
+
+#[cfg( test )]
+mod tests {
+    use super::*;
+    use crate::tr;
+
+    #[test] fn xml_round_trip() {
+        let tree = tr(1) /( tr(2)/tr(3) );
+        let xml = tree.to_xml();
+        assert_eq!( xml, r#"<node data="1"><node data="2"><node data="3"/></node></node>"# );
+        assert_eq!( Tree::from_xml( &xml ).unwrap().to_string(), tree.to_string() );
+
+        let tree = tr( "a<b" ) /( tr( r#"say "hi" & 'bye'"# )/tr( "x\ty\nz" ) ) /tr( "" );
+        let xml = tree.to_xml();
+        assert_eq!( xml, concat!( r#"<node data="a&lt;b"><node data="say &quot;hi&quot; &amp; 'bye'">"#,
+            r#"<node data="x&#9;y&#10;z"/></node><node data=""/></node>"# ));
+        let parsed = Tree::from_xml( &xml ).unwrap();
+        assert_eq!( parsed, tr( "a<b".to_owned() ) /( tr( r#"say "hi" & 'bye'"#.to_owned() )/tr( "x\ty\nz".to_owned() )) /tr( String::new() ));
+        assert!( parsed.validate().is_ok() );
+    }
+
+    #[test] fn deep_xml() {
+        let depth = 200_000;
+        let mut chain = tr( 0.to_string() );
+        for data in 1..depth {
+            chain = tr( data.to_string() ) / chain;
+        }
+        let xml = chain.to_xml();
+        assert!( xml.starts_with( r#"<node data="199999"><node data="199998">"# ));
+        assert_eq!( Tree::from_xml( &xml ).unwrap(), chain );
+    }
+
+    #[test] fn xml_references_and_whitespace() {
+        let xml = "\n<node  data = \"&#65;&#x42;&apos;&gt;\" >\n\t<node data=\"c\" />\n</node >\n";
+        assert_eq!( Tree::from_xml( xml ).unwrap(), tr( "AB'>".to_owned() ) /tr( "c".to_owned() ));
+    }
+
+    #[test] fn xml_errors() {
+        let err = |xml: &str| Tree::from_xml( xml ).unwrap_err().msg;
+        assert_eq!( err( "" ), "xml: no root element" );
+        assert_eq!( err( "x" ), "xml: unexpected 'x'" );
+        assert_eq!( err( "</node>" ), "xml: unexpected </node>" );
+        assert_eq!( err( r#"<node data="1"><node data="2"/>"# ), "xml: missing </node>" );
+        assert_eq!( err( r#"<node data="1"/><node data="2"/>"# ), "xml: unexpected '<' after the root element" );
+        assert_eq!( err( r#"<tree data="1"/>"# ), "xml: expected 'n', found 't'" );
+        assert_eq!( err( "<node/>" ), r#"xml: missing attribute "data""# );
+        assert_eq!( err( r#"<node data="1"# ), "xml: unterminated attribute value" );
+        assert_eq!( err( r#"<node data="<"/>"# ), "xml: unescaped '<' in attribute value" );
+        assert_eq!( err( r#"<node data="&nbsp;"/>"# ), "xml: unknown reference '&nbsp;'" );
+        assert_eq!( err( r#"<node data="&amp"/>"# ), "xml: unterminated reference" );
+        assert_eq!( err( r#"<node data="1" id="2"/>"# ), "xml: expected '>' or '/>', found 'i'" );
+    }
+}